@@ -50,6 +50,7 @@ pub fn main() {
     memset();
     memcpy();
     serde();
+    frames();
     bincode();
     borsh();
     cbor();
@@ -147,6 +148,32 @@ fn serde() {
     );
 }
 
+fn frames() {
+    const COUNT: u32 = 64;
+    let payload = vec![0u8; 1024];
+
+    for (msg, spec) in [
+        ("read_frame", BenchmarkSpec::ReadFrames { count: COUNT }),
+        (
+            "read_frame_into",
+            BenchmarkSpec::ReadFramesInto { count: COUNT },
+        ),
+    ] {
+        let mut builder = ExecutorEnv::builder();
+        builder.write(&spec).unwrap();
+        for _ in 0..COUNT {
+            builder.write_frame(&payload);
+        }
+        let mut exec = ExecutorImpl::from_elf(builder.build().unwrap(), BENCH_ELF).unwrap();
+        let session = exec.run().unwrap();
+        let heap_bytes: u32 = session.journal.unwrap().decode().unwrap();
+        println!(
+            "{msg}({COUNT}): {}, {}, {heap_bytes} heap bytes",
+            session.user_cycles, session.total_cycles
+        );
+    }
+}
+
 fn bincode() {
     let claims = vec![ReceiptClaim::ok(BENCH_ID, vec![0; 1024]); 3];
     let encoded = bincode::serialize(&claims).unwrap();
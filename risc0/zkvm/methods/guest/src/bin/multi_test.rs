@@ -33,7 +33,7 @@ use risc0_zkvm::{
         env::{self, FdReader, FdWriter, Read as _, Write as _},
         memory_barrier, sha,
     },
-    sha::{Digest, Sha256},
+    sha::{Digest, Digestible as _, Sha256},
     Assumption, ReceiptClaim,
 };
 use risc0_zkvm_methods::multi_test::{MultiTestSpec, SYS_MULTI_TEST, SYS_MULTI_TEST_WORDS};
@@ -112,17 +112,164 @@ fn main() {
         MultiTestSpec::Panic => {
             panic!("MultiTestSpec::Panic invoked");
         }
+        MultiTestSpec::PanicCommitter => {
+            env::install_panic_committer();
+            env::commit(&1u32);
+            panic!("MultiTestSpec::PanicCommitter invoked");
+        }
         MultiTestSpec::Fault => unsafe {
             asm!("sw x0, 1(x0)");
         },
+        MultiTestSpec::CommitBigEndian { a, b, c } => {
+            env::commit_u32_be(a);
+            env::commit_u64_be(b);
+            env::commit_u128_be(c);
+        }
+        MultiTestSpec::CommitCbor { id, data } => {
+            env::commit_cbor(&(id, data));
+        }
+        MultiTestSpec::CommitDigests(digests) => {
+            env::commit_digests(&digests);
+        }
+        MultiTestSpec::CycleMarkers => {
+            env::commit_cycle_marker(1);
+            for i in 0..1000u32 {
+                core::hint::black_box(i);
+            }
+            env::commit_cycle_marker(2);
+        }
+        MultiTestSpec::CurrentOutput => {
+            env::commit(&42u32);
+            let output = env::current_output();
+            env::write(&output.digest());
+        }
+        MultiTestSpec::CommitIter { count } => {
+            env::commit_iter((0..count).map(|i| (i, i * i)));
+        }
+        MultiTestSpec::CommitLarge { len } => {
+            let data: alloc::vec::Vec<u8> = (0..len).map(|i| i as u8).collect();
+            env::commit_slice(&data);
+        }
+        MultiTestSpec::CommitVersioned => {
+            env::commit_versioned(3, &(42u64, alloc::string::String::from("hello")));
+        }
+        MultiTestSpec::CycleBudget { limit } => {
+            env::with_cycle_budget(limit, || env::log("within budget"));
+        }
+        MultiTestSpec::ExitWithStatus(status) => {
+            env::commit(&7u32);
+            env::exit_with_status(status);
+        }
+        MultiTestSpec::ExpectMagic { magic } => {
+            let matched = env::expect_magic(&magic).is_ok();
+            let next: u32 = env::read();
+            env::commit(&(matched, next));
+        }
         MultiTestSpec::Halt(exit_code) => {
             env::exit(exit_code);
         }
+        MultiTestSpec::Ensure {
+            holds,
+            exit_code,
+            with_message,
+        } => {
+            if with_message {
+                env::ensure!(holds, exit_code, "MultiTestSpec::Ensure failed");
+            } else {
+                env::ensure!(holds, exit_code);
+            }
+        }
+        MultiTestSpec::Checkpoint => {
+            env::commit(&1u32);
+            let checkpoint = env::checkpoint();
+            env::commit(&2u64);
+            let committed = env::journal_position() - checkpoint.journal_position();
+            env::write(&(checkpoint.journal_position() as u32, committed as u32));
+            env::write(&checkpoint.resumed_since());
+        }
         MultiTestSpec::PauseResume(exit_code) => {
             env::log("before");
             env::pause(exit_code);
             env::log("after");
         }
+        MultiTestSpec::JournalIsFresh => {
+            env::commit(&env::journal_is_fresh());
+            env::commit(&env::journal_is_fresh());
+            env::pause(0);
+            env::commit(&env::journal_is_fresh());
+            env::commit(&env::journal_is_fresh());
+        }
+        MultiTestSpec::JournalBytesHashed => {
+            env::commit_slice(&[0u8; 100]);
+            env::commit(&"abc");
+            env::write(&env::journal_bytes_hashed());
+        }
+        MultiTestSpec::JournalPosition => {
+            env::commit(&1u32);
+            let start = env::journal_position() as u32;
+            env::commit(&"abc");
+            let end = env::journal_position() as u32;
+            env::commit(&(start, end));
+        }
+        MultiTestSpec::ReadString => {
+            env::commit(&env::read_string().ok());
+            env::commit(&env::read_string().ok());
+        }
+        MultiTestSpec::ReadBuffered => {
+            let words: alloc::vec::Vec<u32> = env::read_buffered().unwrap();
+            env::commit(&words);
+        }
+        MultiTestSpec::ReadFrameEndian => {
+            let big = env::read_frame_be();
+            let little = env::read_frame_le();
+            env::commit(&(big, little));
+        }
+        MultiTestSpec::PresenceMask { count } => {
+            let mut mask = 0u64;
+            for i in 0..count {
+                let input: Option<u32> = env::read_option().unwrap();
+                if input.is_some() {
+                    mask |= 1 << i;
+                }
+            }
+            env::commit_presence_mask(mask);
+        }
+        MultiTestSpec::ReadDelimited { delim, max_record } => {
+            let records: alloc::vec::Vec<Result<alloc::vec::Vec<u8>, alloc::string::String>> =
+                core::iter::from_fn(|| env::read_delimited(delim, max_record as usize).transpose())
+                    .map(|record| record.map_err(|err| format!("{err}")))
+                    .collect();
+            env::commit(&records);
+        }
+        MultiTestSpec::Frames { max } => {
+            let frames: alloc::vec::Vec<Result<bool, alloc::string::String>> =
+                env::frames(max as usize)
+                    .map(|frame| frame.map_err(|err| format!("{err}")))
+                    .collect();
+            env::commit(&frames);
+        }
+        MultiTestSpec::ReadOption => {
+            let some: Option<u32> = env::read_option().unwrap();
+            let none: Option<u32> = env::read_option().unwrap();
+            let err: Result<u32, alloc::string::String> = env::read_result().unwrap();
+            let invalid = env::read_option::<u32>().map_err(|err| format!("{err}"));
+            env::commit(&(some, none, err, invalid));
+        }
+        MultiTestSpec::ReadPages => {
+            let mut data = alloc::vec::Vec::new();
+            let mut page_lens = alloc::vec::Vec::new();
+            env::read_pages::<64>(|page| {
+                data.extend_from_slice(page);
+                page_lens.push(page.len());
+                true
+            });
+            env::commit(&(data, page_lens));
+        }
+        MultiTestSpec::ReadSliceExact { nwords } => {
+            let mut buf = vec![0u32; nwords as usize];
+            let filled = env::read_slice_exact(&mut buf).is_ok();
+            env::commit(&(filled, buf));
+        }
         MultiTestSpec::ReadWriteMem { values } => {
             for (addr, value) in values.into_iter() {
                 if value != 0 {
@@ -135,6 +282,9 @@ fn main() {
                 }
             }
         }
+        MultiTestSpec::Sha256 { data } => {
+            env::commit(&env::sha256(&data));
+        }
         MultiTestSpec::ShaDigest { data } => {
             let digest = sha::Impl::hash_bytes(&data);
             env::commit(&digest);
@@ -157,12 +307,26 @@ fn main() {
                 input_len = input.len();
             }
         }
+        MultiTestSpec::SyscallCount => {
+            let before = env::syscall_count();
+            env::log("first");
+            env::log("second");
+            let after = env::syscall_count();
+            env::commit(&(after - before));
+        }
         MultiTestSpec::SyscallWords => {
             let input: &[u64] = &[0x0102030405060708];
 
             let host_data = env::send_recv_slice::<u64, u32>(SYS_MULTI_TEST_WORDS, &input);
             assert_eq!(host_data, &[0x05060708, 0x01020304]);
         }
+        MultiTestSpec::SyscallWordsSlop => {
+            // The host echoes 6 bytes, which is not a whole number of words.
+            let input: &[u8] = &[1, 2, 3, 4, 5, 6];
+
+            let host_data = env::try_send_recv_slice::<u8, u32>(SYS_MULTI_TEST_WORDS, input);
+            assert!(host_data.is_err());
+        }
         MultiTestSpec::DoRandom => {
             // Test random number generation in the zkvm
             // Test for a combination of lengths and data alignments to make sure all cases
@@ -181,6 +345,12 @@ fn main() {
                 }
             }
         }
+        MultiTestSpec::StdinPeekLen => {
+            let before = env::stdin_peek_len();
+            let mut buf = [0u8; 3];
+            env::read_slice(&mut buf);
+            env::commit(&(before, env::stdin_peek_len()));
+        }
         MultiTestSpec::SysInput(digest) => {
             assert_eq!(env::input_digest(), digest);
         }
@@ -202,11 +372,18 @@ fn main() {
                 env::verify(image_id, &journal).unwrap();
             }
         }
+        MultiTestSpec::SysVerifyAndCommit { image_id, journal } => {
+            env::verify_and_commit(image_id, &journal).unwrap();
+        }
         MultiTestSpec::SysVerifyIntegrity { claim_words } => {
             let claim: ReceiptClaim = risc0_zkvm::serde::from_slice(&claim_words).unwrap();
             // NOTE: This panic string is used in a test.
             env::verify_integrity(&claim).expect("env::verify_integrity returned error");
         }
+        MultiTestSpec::SysVerifyFirstMatching { candidates, wanted } => {
+            let index = env::verify_first_matching(&candidates, |claim, _| *claim == wanted);
+            env::commit(&index.map(|index| index as u32));
+        }
         MultiTestSpec::SysVerifyAssumption { assumption_words } => {
             let assumption: Assumption = risc0_zkvm::serde::from_slice(&assumption_words).unwrap();
             env::verify_assumption(assumption.claim, assumption.control_root).unwrap();
@@ -259,6 +436,14 @@ fn main() {
             }
             env::commit_slice(&result);
         }
+        MultiTestSpec::MerkleRoot {
+            leaf,
+            path,
+            root,
+            index,
+        } => {
+            env::commit_merkle_root(&leaf, &path, root, env::MerklePathOrder::Indexed(index));
+        }
         MultiTestSpec::LibM => {
             use core::hint::black_box;
             let f = black_box(1.0_f32);
@@ -321,10 +506,21 @@ fn main() {
                 in("x14") 10000,
             );
         },
+        MultiTestSpec::WriteSliceTo { fd, words } => {
+            env::write_slice_to(fd, &words);
+        }
         MultiTestSpec::SysLogInvalidAddr => unsafe {
             let addr: *const u8 = SYSTEM.start() as _;
             sys_log(addr, 100);
         },
+        MultiTestSpec::AggregateJournalDigests(digests) => {
+            let digest = env::aggregate_journal_digests(&digests);
+            env::commit(&digest);
+        }
+        MultiTestSpec::ArrayRoundTrip => {
+            let words: [u32; 8] = env::read_array();
+            env::commit_array(&words);
+        }
         MultiTestSpec::AlignedAlloc => {
             #[repr(align(1024))]
             struct AlignTest1 {
@@ -373,18 +569,30 @@ fn main() {
                 assert_eq!(buf, MSG);
             }
         }
-        MultiTestSpec::SysForkFork => {
-            let pid = sys_fork();
-            if pid == 0 {
-                sys_fork();
-            }
-        }
+        MultiTestSpec::SysForkFork => loop {
+            // Each child forks again, without limit.
+            sys_fork();
+        },
         MultiTestSpec::SysForkJournalPanic => {
             let pid = sys_fork();
             if pid == 0 {
                 env::commit_slice(b"should panic");
             }
         }
+        MultiTestSpec::RunUnconstrainedInline => {
+            // Commits whether the closure's change to memory is visible after it returns.
+            let mut ran = false;
+            env::run_unconstrained(|| ran = true);
+            env::commit(&ran);
+        }
+        MultiTestSpec::RunUnconstrainedNested => {
+            env::run_unconstrained(|| {
+                env::run_unconstrained(|| env::write_slice(b"inner;"));
+                env::write_slice(b"outer;");
+            });
+            env::write_slice(b"parent;");
+            env::commit(&42u32);
+        }
         MultiTestSpec::RunUnconstrained {
             unconstrained,
             cycles,
@@ -435,6 +643,18 @@ fn main() {
             env::verify_assumption(claim_digest, control_root)
                 .expect("env::verify_integrity returned error");
         }
+        MultiTestSpec::Keccak256 { data } => {
+            let result = env::keccak256(&data).map_err(|err| format!("{err}"));
+            env::commit(&result);
+        }
+        MultiTestSpec::KeccakSummary => {
+            env::commit(&1u32);
+            env::keccak256(b"abc").unwrap();
+            env::keccak256(&[7u8; 300]).unwrap();
+            #[allow(static_mut_refs)]
+            let batcher = unsafe { &env::KECCAK_BATCHER };
+            env::commit_keccak_summary(batcher);
+        }
         MultiTestSpec::SysKeccak => {
             let expected: [u8; DIGEST_BYTES] = [
                 71, 23, 50, 133, 168, 215, 52, 30, 94, 151, 47, 198, 119, 40, 99, 132, 248, 2, 248,
@@ -480,6 +700,11 @@ fn main() {
                 hex!("420e6b2cc4cd396ecf6b7e4c8b4c1c1e88c3589534b581fd133793a6e53006f1")
             );
         }
+        MultiTestSpec::Tee { bytes } => {
+            let mut tee = env::tee(env::stdout(), env::journal());
+            tee.write(&(bytes.len() as u32));
+            tee.write_slice(&bytes);
+        }
         MultiTestSpec::TinyKeccak => {
             // test_keccak_01.txt
             let mut hasher = Keccak::v256();
@@ -42,6 +42,12 @@ pub enum BenchmarkSpec {
     },
     Read,
     ReadFramed,
+    ReadFrames {
+        count: u32,
+    },
+    ReadFramesInto {
+        count: u32,
+    },
     #[cfg(feature = "std")]
     ReadBuffered,
     #[cfg(feature = "std")]
@@ -98,6 +104,19 @@ impl BenchmarkSpec {
                 let claims: Vec<ReceiptClaim> = env::read_framed().unwrap();
                 memory_barrier(&claims);
             }
+            BenchmarkSpec::ReadFrames { count } => {
+                for _ in 0..count {
+                    let bytes = env::read_frame();
+                    memory_barrier(&bytes);
+                }
+            }
+            BenchmarkSpec::ReadFramesInto { count } => {
+                let mut bytes = Vec::new();
+                for _ in 0..count {
+                    env::read_frame_into(&mut bytes);
+                    memory_barrier(&bytes);
+                }
+            }
             #[cfg(feature = "std")]
             BenchmarkSpec::ReadBuffered => {
                 let claims: Vec<ReceiptClaim> = env::read_buffered().unwrap();
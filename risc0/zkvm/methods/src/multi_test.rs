@@ -23,7 +23,9 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum MultiTestSpec {
+    AggregateJournalDigests(Vec<Digest>),
     AlignedAlloc,
+    ArrayRoundTrip,
     AllocZeroed,
     BigInt {
         x: [u32; bigint::WIDTH_WORDS],
@@ -35,6 +37,29 @@ pub enum MultiTestSpec {
         /// Busy loop until the guest has run for at least this number of cycles
         cycles: u64,
     },
+    Checkpoint,
+    CommitBigEndian {
+        a: u32,
+        b: u64,
+        c: u128,
+    },
+    CommitCbor {
+        id: u32,
+        data: Vec<u8>,
+    },
+    CommitDigests(Vec<Digest>),
+    CommitIter {
+        count: u32,
+    },
+    CommitLarge {
+        len: u32,
+    },
+    CommitVersioned,
+    CurrentOutput,
+    CycleMarkers,
+    CycleBudget {
+        limit: u64,
+    },
     DoNothing,
     DoRandom,
     Echo {
@@ -48,22 +73,65 @@ pub enum MultiTestSpec {
         fd: u32,
         nwords: u32,
     },
+    Ensure {
+        holds: bool,
+        exit_code: u8,
+        with_message: bool,
+    },
     EventTrace,
+    ExitWithStatus(u32),
+    ExpectMagic {
+        magic: Vec<u8>,
+    },
     Fault,
+    Frames {
+        max: u32,
+    },
     Halt(u8),
+    JournalBytesHashed,
+    JournalIsFresh,
+    JournalPosition,
+    Keccak256 {
+        data: Vec<u8>,
+    },
+    KeccakSummary,
     LibM,
+    MerkleRoot {
+        leaf: Vec<u8>,
+        path: Vec<Digest>,
+        root: Digest,
+        index: u64,
+    },
     Oom,
     OutOfBounds,
     OutOfBoundsEcall,
     Panic,
+    PanicCommitter,
     PauseResume(u8),
     Profiler,
+    PresenceMask {
+        count: u32,
+    },
+    ReadBuffered,
+    ReadDelimited {
+        delim: u8,
+        max_record: u32,
+    },
+    ReadFrameEndian,
+    ReadOption,
+    ReadPages,
+    ReadString,
+    ReadSliceExact {
+        nwords: u32,
+    },
     ReadWriteMem {
         /// Tuples of (address, value). Zero means read the value and
         /// output it; nonzero means write that value.
         values: Vec<(u32, u32)>,
     },
     RsaCompat,
+    RunUnconstrainedInline,
+    RunUnconstrainedNested,
     RunUnconstrained {
         // True to actually call run_unconstrained, false to run the busy loop directly as a control.
         unconstrained: bool,
@@ -73,7 +141,12 @@ pub enum MultiTestSpec {
     Syscall {
         count: u32,
     },
+    SyscallCount,
     SyscallWords,
+    SyscallWordsSlop,
+    Sha256 {
+        data: Vec<u8>,
+    },
     ShaConforms,
     ShaCycleCount,
     ShaDigest {
@@ -107,7 +180,18 @@ pub enum MultiTestSpec {
         // Position and length to do reads
         pos_and_len: Vec<(u32, u32)>,
     },
+    StdinPeekLen,
     SysVerify(Vec<(Digest, Vec<u8>)>),
+    SysVerifyAndCommit {
+        image_id: Digest,
+        journal: Vec<u8>,
+    },
+    SysVerifyFirstMatching {
+        // Tuples of (claim, control root) to choose from.
+        candidates: Vec<(Digest, Digest)>,
+        // Claim to select.
+        wanted: Digest,
+    },
     SysVerifyIntegrity {
         // ReceiptClaim: Field is serialized to avoid circular dependency issues.
         claim_words: Vec<u32>,
@@ -116,8 +200,15 @@ pub enum MultiTestSpec {
         // Assumption: Field is serialized to avoid circular dependency issues.
         assumption_words: Vec<u32>,
     },
+    Tee {
+        bytes: Vec<u8>,
+    },
     TinyKeccak,
     TooManySha,
+    WriteSliceTo {
+        fd: u32,
+        words: Vec<u32>,
+    },
 }
 
 declare_syscall!(pub SYS_MULTI_TEST);
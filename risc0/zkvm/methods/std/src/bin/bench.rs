@@ -14,8 +14,20 @@
 
 use risc0_zkvm::guest::env;
 use risc0_zkvm_methods::bench::BenchmarkSpec;
+use risc0_zkvm_platform::syscall::sys_alloc_aligned;
+
+/// Returns the current position of the guest's bump allocator.
+///
+/// The heap never frees memory, so the difference between two positions is the
+/// number of heap bytes allocated in between.
+fn heap_pos() -> usize {
+    unsafe { sys_alloc_aligned(0, 1) as usize }
+}
 
 fn main() {
     let spec: BenchmarkSpec = env::read();
+    let start = heap_pos();
     spec.run();
+    // Commit the number of heap bytes allocated while running the benchmark.
+    env::commit(&((heap_pos() - start) as u32));
 }
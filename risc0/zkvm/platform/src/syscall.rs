@@ -144,6 +144,8 @@ pub mod nr {
     declare_syscall!(pub SYS_PROVE_ZKR);
     declare_syscall!(pub SYS_RANDOM);
     declare_syscall!(pub SYS_READ);
+    declare_syscall!(pub SYS_STDIN_LEN);
+    declare_syscall!(pub SYS_SYSCALL_COUNT);
     declare_syscall!(pub SYS_VERIFY_INTEGRITY);
     declare_syscall!(pub SYS_WRITE);
 }
@@ -457,6 +459,25 @@ pub extern "C" fn sys_cycle_count() -> u64 {
     ((hi as u64) << 32) + lo as u64
 }
 
+/// Returns the number of bytes remaining on stdin, or -1 if the host does not
+/// know the length of stdin.
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub extern "C" fn sys_stdin_len() -> i64 {
+    let Return(known, len) = unsafe { syscall_0(nr::SYS_STDIN_LEN, null_mut(), 0) };
+    if known == 0 {
+        -1
+    } else {
+        len as i64
+    }
+}
+
+/// Returns the number of syscalls the host has handled before this one.
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub extern "C" fn sys_syscall_count() -> u64 {
+    let Return(hi, lo) = unsafe { syscall_0(nr::SYS_SYSCALL_COUNT, null_mut(), 0) };
+    ((hi as u64) << 32) + lo as u64
+}
+
 /// Reads the given number of bytes into the given buffer, posix-style.  Returns
 /// the number of bytes actually read.  On end of file, returns 0.
 ///
@@ -12,93 +12,222 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
-use anyhow::Result;
-use risc0_zkvm_platform::syscall::DIGEST_BYTES;
+use anyhow::{anyhow, Result};
 
 use crate::sha::Digest;
 
 /// This struct implements the batching of calls to the keccak accelerator.
+///
+/// The input transcript is allocated on the heap when data is first written, so
+/// a batcher that is never used, such as `env::KECCAK_BATCHER` in a guest that
+/// does not use keccak, does not add [KeccakBatcher::KECCAK_LIMIT] bytes to the
+/// memory image.
 pub struct KeccakBatcher {
-    input_transcript: [u8; Self::KECCAK_LIMIT],
+    input_transcript: Vec<u8>,
     block_count_offset: usize,
     data_offset: usize,
+    message_spans: Option<Vec<(usize, usize)>>,
+    rate: usize,
+    permutation: Option<fn(&mut [u64; 25])>,
 }
 
 impl Default for KeccakBatcher {
     /// create a new instance of a batcher with an input transcript region
     fn default() -> Self {
+        Self::init()
+    }
+}
+
+impl KeccakBatcher {
+    /// create a new instance of a batcher in a const context.
+    pub const fn init() -> Self {
         Self {
-            input_transcript: [0u8; Self::KECCAK_LIMIT],
+            input_transcript: Vec::new(),
             block_count_offset: 0,
             data_offset: Self::BLOCK_COUNT_BYTES,
+            message_spans: None,
+            rate: Self::BLOCK_BYTES,
+            permutation: None,
         }
     }
-}
 
-impl KeccakBatcher {
-    pub const fn init() -> Self {
+    /// create a new instance of a batcher that records the span of each
+    /// message written to the input transcript.
+    ///
+    /// See [KeccakBatcher::message_spans].
+    pub const fn with_message_spans() -> Self {
         Self {
-            input_transcript: [0u8; Self::KECCAK_LIMIT],
+            input_transcript: Vec::new(),
             block_count_offset: 0,
             data_offset: Self::BLOCK_COUNT_BYTES,
+            message_spans: Some(Vec::new()),
+            rate: Self::BLOCK_BYTES,
+            permutation: None,
         }
     }
 
+    /// set the sponge rate, in bytes, used to pad messages into blocks.
+    ///
+    /// The default is [KeccakBatcher::BLOCK_BYTES], the rate of keccak-256.
+    /// Other members of the keccak family use a different rate, e.g. 72 for
+    /// keccak-512 and 104 for keccak-384. The digest written for each message
+    /// is half the capacity, see [KeccakBatcher::digest_bytes].
+    ///
+    /// Panics if the rate is zero, not a multiple of 8, or larger than the
+    /// 200 byte keccak state. The rate must be set before any data is written.
+    pub fn with_rate(mut self, rate: usize) -> Self {
+        assert!(
+            rate != 0 && rate % 8 == 0 && rate <= Self::STATE_BYTES,
+            "invalid keccak rate: {rate}"
+        );
+        assert!(!self.has_data(), "rate must be set before writing data");
+        self.rate = rate;
+        self
+    }
+
+    /// set the keccak-f\[1600\] permutation used by [KeccakBatcher::hash].
+    ///
+    /// The permutation receives the 25 lanes of the keccak state, where lane
+    /// `(x, y)` is at index `x + 5 * y`. Message bytes are absorbed into the
+    /// lanes in little-endian order, i.e. byte `i` of a block is XORed into
+    /// byte `i % 8` of lane `i / 8`, and the digest is squeezed out the same
+    /// way. This lets a guest supply an optimized or accelerated permutation
+    /// while the batcher drives the sponge and records the transcript.
+    pub fn with_permutation(mut self, permutation: fn(&mut [u64; 25])) -> Self {
+        self.permutation = Some(permutation);
+        self
+    }
+
+    /// Maximum size of the input transcript region, in bytes.
     pub const KECCAK_LIMIT: usize = 100_000;
+
+    /// Size of the block count header preceding each keccak entry, in bytes.
     pub const BLOCK_COUNT_BYTES: usize = 8;
+
+    /// Size of a keccak block, in bytes.
+    ///
+    /// 136 is the rate of keccak-256 (1600 bit state minus 512 bit capacity).
+    /// Data and padding are always written in multiples of this size.
     pub const BLOCK_BYTES: usize = 136;
+
+    /// Size of the keccak-f\[1600\] state, in bytes.
+    pub const STATE_BYTES: usize = 200;
+
+    /// Size of the zeroed block count that terminates the transcript, in bytes.
     pub const FINAL_PADDING_BYTES: usize = 8;
 
+    /// Maximum number of blocks in a single keccak entry.
+    ///
+    /// The block count of an entry is stored in a single byte of its header,
+    /// so a message that pads to more blocks is rejected.
+    pub const MAX_ENTRY_BLOCKS: usize = u8::MAX as usize;
+
     /// write data to the input transcript.
     ///
     /// This is meant to be used by lower-level functions within keccak crates.
     /// Many keccak crates will write raw data and padding to a 1600 bit buffer
     /// often called the "state". All data and padding written to the state
     /// should be passed to this function.
+    ///
+    /// The transcript grows on demand, doubling its capacity up to
+    /// [KeccakBatcher::KECCAK_LIMIT] bytes, so a batcher that hashes little
+    /// data does not allocate the full limit.
     fn write_data(&mut self, input: &[u8]) -> Result<()> {
-        self.input_transcript[self.data_offset..self.data_offset + input.len()]
-            .copy_from_slice(input);
-        self.data_offset += input.len();
+        let end = self.data_offset + input.len();
+        if end > Self::KECCAK_LIMIT {
+            return Err(anyhow!(
+                "keccak transcript exceeds {} bytes",
+                Self::KECCAK_LIMIT
+            ));
+        }
+        let capacity = self.input_transcript.capacity();
+        if end > capacity {
+            let target = (2 * capacity).max(end).min(Self::KECCAK_LIMIT);
+            self.input_transcript
+                .reserve_exact(target - self.input_transcript.len());
+        }
+        self.input_transcript.resize(self.data_offset, 0);
+        self.input_transcript.extend_from_slice(input);
+        self.data_offset = end;
 
         Ok(())
     }
 
     /// write padding to the input transcript.
     ///
-    /// Pad the raw input with the delimitor, 0x00 bytes, and a 0x80 byte. This
-    /// will pad the raw data upto the current block boundary.
+    /// Pad the raw input up to the current block boundary, see
+    /// [KeccakBatcher::padding].
     fn write_padding(&mut self) -> Result<()> {
-        self.write_data(&[0x01])?;
-        let data_length = self.current_data_length();
-        let remaining_bytes = Self::BLOCK_BYTES - (data_length % Self::BLOCK_BYTES);
-
-        let zeroes = vec![0u8; remaining_bytes - 1];
+        let padding = Self::padding(self.current_data_length(), self.rate);
+        self.write_data(&padding)
+    }
 
-        self.write_data(&zeroes)?;
-        self.write_data(&[0x80])?;
+    /// returns the number of padding bytes for a message of `len` bytes at the
+    /// given rate.
+    ///
+    /// This is between 1 and `rate` bytes, filling the last block of the
+    /// message.
+    pub const fn padding_len(len: usize, rate: usize) -> usize {
+        rate - (len % rate)
+    }
 
-        Ok(())
+    /// returns the keccak padding for a message of `len` bytes at the given
+    /// rate.
+    ///
+    /// The padding is the delimiter 0x01, 0x00 bytes, and a final 0x80 byte,
+    /// [KeccakBatcher::padding_len] bytes in total. When a single byte is left
+    /// in the last block, the delimiter and final byte are combined into 0x81.
+    pub fn padding(len: usize, rate: usize) -> Vec<u8> {
+        let mut padding = vec![0u8; Self::padding_len(len, rate)];
+        padding[0] |= 0x01;
+        *padding.last_mut().unwrap() |= 0x80;
+        padding
     }
 
     /// write keccak hash to the transcript, updating the block count.
     ///
     /// the amount of raw data written to the
     pub fn write_keccak_entry(&mut self, input: &[u8], hash: &[u8; 32]) -> Result<()> {
+        self.write_entry(input, hash)
+    }
+
+    fn write_entry(&mut self, input: &[u8], hash: &[u8]) -> Result<()> {
+        if hash.len() != self.digest_bytes() {
+            return Err(anyhow!(
+                "keccak output must be {} bytes, got {}",
+                self.digest_bytes(),
+                hash.len()
+            ));
+        }
+
+        // the block count is stored in the first byte of the entry header.
+        let padding_bytes = Self::padding_len(input.len(), self.rate);
+        let blocks = (input.len() + padding_bytes) / self.rate;
+        if blocks > Self::MAX_ENTRY_BLOCKS {
+            return Err(anyhow!(
+                "keccak entry of {blocks} blocks exceeds the limit of {}",
+                Self::MAX_ENTRY_BLOCKS
+            ));
+        }
+
         // if this entry does not fit in the remaining space, create a new claim and reset the batcher.
-        let padding_bytes = Self::BLOCK_BYTES - (input.len() % Self::BLOCK_BYTES);
-        if self.data_offset + input.len() + padding_bytes + DIGEST_BYTES + Self::FINAL_PADDING_BYTES
+        if self.data_offset + input.len() + padding_bytes + hash.len() + Self::FINAL_PADDING_BYTES
             > Self::KECCAK_LIMIT
         {
             let _digest = self.finalize_transcript();
         }
 
+        if let Some(spans) = &mut self.message_spans {
+            spans.push((self.data_offset, input.len()));
+        }
         self.write_data(input)?;
         self.write_padding()?;
 
         let data_length = self.current_data_length();
-        let block_count = (data_length / Self::BLOCK_BYTES) as u8;
+        let block_count = u8::try_from(data_length / self.rate)
+            .map_err(|_| anyhow!("keccak entry exceeds {} blocks", Self::MAX_ENTRY_BLOCKS))?;
 
         self.write_data(hash)?;
         self.input_transcript[self.block_count_offset] = block_count;
@@ -108,16 +237,96 @@ impl KeccakBatcher {
         Ok(())
     }
 
+    /// write a message and its keccak digest to the transcript.
+    ///
+    /// This performs the full data, padding and hash sequence for a single
+    /// message. `keccak_output` must be the keccak digest of `message`, as
+    /// computed by the guest, and [KeccakBatcher::digest_bytes] long. For the
+    /// default rate, this is the 32 byte keccak-256 digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use risc0_zkvm::guest::env::KeccakBatcher;
+    /// use tiny_keccak::{Hasher, Keccak};
+    ///
+    /// let mut batcher = KeccakBatcher::default();
+    /// for message in [b"hello".as_slice(), b"world".as_slice()] {
+    ///     let mut output = [0u8; 32];
+    ///     let mut keccak = Keccak::v256();
+    ///     keccak.update(message);
+    ///     keccak.finalize(&mut output);
+    ///     batcher.absorb(message, &output).unwrap();
+    /// }
+    /// let digest = batcher.finalize_transcript();
+    /// ```
+    pub fn absorb(&mut self, message: &[u8], keccak_output: &[u8]) -> Result<()> {
+        self.write_entry(message, keccak_output)
+    }
+
+    /// hash a message with the permutation set by [KeccakBatcher::with_permutation]
+    /// and write it and its digest to the transcript.
+    ///
+    /// The message is padded and absorbed at the rate of the batcher, and
+    /// [KeccakBatcher::digest_bytes] of output are squeezed from the state and
+    /// returned. Returns an error if no permutation was set.
+    pub fn hash(&mut self, message: &[u8]) -> Result<Vec<u8>> {
+        let permutation = self
+            .permutation
+            .ok_or_else(|| anyhow!("no keccak permutation set"))?;
+
+        let mut padded = message.to_vec();
+        padded.extend_from_slice(&Self::padding(message.len(), self.rate));
+
+        let mut state = [0u64; 25];
+        for block in padded.chunks_exact(self.rate) {
+            for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+                *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+            }
+            permutation(&mut state);
+        }
+
+        let mut output = Vec::with_capacity(self.digest_bytes());
+        loop {
+            for lane in &state[..self.rate / 8] {
+                output.extend_from_slice(&lane.to_le_bytes());
+            }
+            if output.len() >= self.digest_bytes() {
+                break;
+            }
+            permutation(&mut state);
+        }
+        output.truncate(self.digest_bytes());
+
+        self.write_entry(message, &output)?;
+        Ok(output)
+    }
+
+    /// returns the sponge rate of the batcher, in bytes.
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// returns the size of the digest written for each message, in bytes.
+    ///
+    /// This is half the capacity of the sponge, i.e. 32 bytes for keccak-256
+    /// and 64 bytes for keccak-512.
+    pub fn digest_bytes(&self) -> usize {
+        (Self::STATE_BYTES - self.rate) / 2
+    }
+
     /// get the digest of the input transcript
+    ///
+    /// Only complete keccak entries are included. Any data written after the
+    /// last complete entry is discarded, so a guest that wrote data
+    /// speculatively can finalize without including the unfinished permutation.
     pub fn finalize_transcript(&mut self) -> Digest {
         use risc0_zkp::core::hash::sha::Sha256;
 
+        self.input_transcript.truncate(self.block_count_offset);
         self.input_transcript
-            [self.block_count_offset..self.block_count_offset + Self::BLOCK_COUNT_BYTES]
-            .copy_from_slice(&[0u8; Self::BLOCK_COUNT_BYTES]);
-        let transcript_digest = crate::sha::Impl::hash_bytes(
-            &self.input_transcript[0..self.block_count_offset + Self::BLOCK_COUNT_BYTES],
-        );
+            .resize(self.block_count_offset + Self::BLOCK_COUNT_BYTES, 0);
+        let transcript_digest = crate::sha::Impl::hash_bytes(&self.input_transcript);
 
         // TODO: add assumption, send transcript
         // crate::guest::env::verify_assumption(*transcript_digest, Digest::default()).unwrap();
@@ -125,9 +334,59 @@ impl KeccakBatcher {
         *transcript_digest
     }
 
+    /// returns the (offset, length) of each message in the input transcript, if
+    /// the batcher was created with [KeccakBatcher::with_message_spans].
+    ///
+    /// The spans are cleared when the transcript is finalized, so they should be
+    /// read before calling [KeccakBatcher::finalize_transcript].
+    pub fn message_spans(&self) -> Option<&[(usize, usize)]> {
+        self.message_spans.as_deref()
+    }
+
+    /// returns the complete keccak entries written to the input transcript.
+    ///
+    /// Data written after the last complete entry is not included. Finalizing
+    /// the transcript hashes these bytes followed by a zeroed block count
+    /// header.
+    pub fn transcript(&self) -> &[u8] {
+        &self.input_transcript[..self.block_count_offset]
+    }
+
+    /// returns the total number of keccak blocks in the complete entries of the
+    /// input transcript.
+    ///
+    /// Each block is one keccak permutation to be proven.
+    pub fn block_count(&self) -> u32 {
+        let transcript = self.transcript();
+        let mut offset = 0;
+        let mut total = 0;
+        while offset < transcript.len() {
+            let blocks = transcript[offset];
+            total += u32::from(blocks);
+            offset += Self::BLOCK_COUNT_BYTES + blocks as usize * self.rate + self.digest_bytes();
+        }
+        total
+    }
+
+    /// get the digest of the input transcript without finalizing it.
+    ///
+    /// This is the digest [KeccakBatcher::finalize_transcript] would return,
+    /// excluding any data written after the last complete keccak entry.
+    pub fn transcript_digest(&self) -> Digest {
+        use risc0_zkp::core::hash::sha::Sha256;
+
+        let mut transcript = self.transcript().to_vec();
+        transcript.extend_from_slice(&[0u8; Self::BLOCK_COUNT_BYTES]);
+        *crate::sha::Impl::hash_bytes(&transcript)
+    }
+
     fn reset(&mut self) {
+        self.input_transcript.clear();
         self.block_count_offset = 0;
         self.data_offset = Self::BLOCK_COUNT_BYTES;
+        if let Some(spans) = &mut self.message_spans {
+            spans.clear();
+        }
     }
 
     fn current_data_length(&self) -> usize {
@@ -140,3 +399,239 @@ impl KeccakBatcher {
         self.data_offset != Self::BLOCK_COUNT_BYTES
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::KeccakBatcher;
+
+    #[test]
+    fn finalize_excludes_trailing_data() {
+        let mut expected = KeccakBatcher::default();
+        expected.write_keccak_entry(b"hello", &[1u8; 32]).unwrap();
+        let expected = expected.finalize_transcript();
+
+        let mut batcher = KeccakBatcher::default();
+        batcher.write_keccak_entry(b"hello", &[1u8; 32]).unwrap();
+        batcher.write_data(b"speculative").unwrap();
+        assert!(batcher.has_data());
+        assert_eq!(batcher.finalize_transcript(), expected);
+        assert!(!batcher.has_data());
+    }
+
+    #[test]
+    fn message_spans() {
+        let mut batcher = KeccakBatcher::with_message_spans();
+        batcher.write_keccak_entry(b"one", &[1u8; 32]).unwrap();
+        batcher.write_keccak_entry(&[2u8; 200], &[2u8; 32]).unwrap();
+        batcher.write_keccak_entry(b"three", &[3u8; 32]).unwrap();
+
+        // Each entry is a block count header, the padded message, and the hash.
+        let header = KeccakBatcher::BLOCK_COUNT_BYTES;
+        let block = KeccakBatcher::BLOCK_BYTES;
+        let first = header;
+        let second = first + block + 32 + header;
+        let third = second + 2 * block + 32 + header;
+        assert_eq!(
+            batcher.message_spans().unwrap(),
+            &[(first, 3), (second, 200), (third, 5)]
+        );
+
+        batcher.finalize_transcript();
+        assert!(batcher.message_spans().unwrap().is_empty());
+        assert!(KeccakBatcher::default().message_spans().is_none());
+    }
+
+    #[test]
+    fn transcript() {
+        use risc0_zkp::core::hash::sha::Sha256;
+
+        let mut batcher = KeccakBatcher::default();
+        assert!(batcher.transcript().is_empty());
+        batcher.write_keccak_entry(b"hello", &[1u8; 32]).unwrap();
+        batcher.write_keccak_entry(b"world", &[2u8; 32]).unwrap();
+
+        let entry = KeccakBatcher::BLOCK_COUNT_BYTES + KeccakBatcher::BLOCK_BYTES + 32;
+        assert_eq!(batcher.transcript().len(), 2 * entry);
+        assert_eq!(batcher.transcript()[0], 1);
+        assert_eq!(
+            &batcher.transcript()[KeccakBatcher::BLOCK_COUNT_BYTES..][..5],
+            b"hello"
+        );
+
+        let mut hashed = batcher.transcript().to_vec();
+        hashed.extend_from_slice(&[0u8; KeccakBatcher::BLOCK_COUNT_BYTES]);
+        let expected = *crate::sha::Impl::hash_bytes(&hashed);
+        assert_eq!(batcher.finalize_transcript(), expected);
+    }
+
+    #[test]
+    fn block_count() {
+        let mut batcher = KeccakBatcher::default();
+        assert_eq!(batcher.block_count(), 0);
+        batcher.write_keccak_entry(b"abc", &[1u8; 32]).unwrap();
+        batcher.write_keccak_entry(&[2u8; 300], &[2u8; 32]).unwrap();
+        batcher.write_data(b"speculative").unwrap();
+        assert_eq!(batcher.block_count(), 4);
+
+        let digest = batcher.transcript_digest();
+        assert_eq!(batcher.finalize_transcript(), digest);
+        assert_eq!(batcher.block_count(), 0);
+    }
+
+    #[test]
+    fn max_entry_blocks() {
+        let block = KeccakBatcher::BLOCK_BYTES;
+
+        // 255 blocks, including at least one byte of padding, is the largest entry.
+        let mut batcher = KeccakBatcher::default();
+        batcher
+            .write_keccak_entry(&vec![1u8; 255 * block - 1], &[1u8; 32])
+            .unwrap();
+        assert_eq!(batcher.block_count(), 255);
+
+        // The padding of a message of exactly 255 blocks takes a 256th block.
+        let mut batcher = KeccakBatcher::default();
+        let err = batcher
+            .write_keccak_entry(&vec![1u8; 255 * block], &[1u8; 32])
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds the limit"));
+        assert!(!batcher.has_data());
+    }
+
+    #[test]
+    fn padding_at_rate_minus_one() {
+        use sha3::{Digest as _, Keccak256};
+
+        let block = KeccakBatcher::BLOCK_BYTES;
+        assert_eq!(KeccakBatcher::padding(block - 1, block), [0x81]);
+        assert_eq!(KeccakBatcher::padding(block - 2, block), [0x01, 0x80]);
+        assert_eq!(KeccakBatcher::padding(block, block).len(), block);
+
+        // A message leaving one byte in its block is padded within that block.
+        let message = vec![4u8; block - 1];
+        let hash: [u8; 32] = Keccak256::digest(&message).into();
+        let mut batcher = KeccakBatcher::default();
+        batcher.write_keccak_entry(&message, &hash).unwrap();
+        assert_eq!(batcher.block_count(), 1);
+
+        let header = KeccakBatcher::BLOCK_COUNT_BYTES;
+        let mut transcript = vec![0u8; header];
+        transcript[0] = 1;
+        transcript.extend_from_slice(&message);
+        transcript.push(0x81);
+        transcript.extend_from_slice(&hash);
+        assert_eq!(batcher.transcript(), transcript);
+    }
+
+    #[test]
+    fn absorb_rejects_short_output() {
+        let mut batcher = KeccakBatcher::default();
+        assert!(batcher.absorb(b"hello", &[0u8; 31]).is_err());
+        assert!(!batcher.has_data());
+    }
+
+    #[test]
+    fn keccak512_rate() {
+        use risc0_zkp::core::hash::sha::Sha256;
+        use sha3::{Digest as _, Keccak512};
+
+        let message = [5u8; 100];
+        let hash = Keccak512::digest(message);
+
+        let mut batcher = KeccakBatcher::default().with_rate(72);
+        assert_eq!(batcher.digest_bytes(), 64);
+        assert!(batcher.write_keccak_entry(&message, &[0u8; 32]).is_err());
+        batcher.absorb(&message, &hash).unwrap();
+
+        // 100 bytes of data and padding span two 72 byte blocks.
+        let header = KeccakBatcher::BLOCK_COUNT_BYTES;
+        let mut transcript = vec![0u8; header];
+        transcript[0] = 2;
+        transcript.extend_from_slice(&message);
+        transcript.push(0x01);
+        transcript.resize(header + 2 * 72 - 1, 0);
+        transcript.push(0x80);
+        transcript.extend_from_slice(&hash);
+        transcript.resize(transcript.len() + header, 0);
+
+        assert_eq!(
+            batcher.finalize_transcript(),
+            *crate::sha::Impl::hash_bytes(&transcript)
+        );
+    }
+
+    #[test]
+    fn hash_with_permutation() {
+        // With an identity permutation, the state is the XOR of the padded blocks.
+        let message = [3u8; 150];
+        let mut expected = [3u8; 136];
+        expected[..14].fill(3 ^ 3);
+        expected[14] ^= 0x01;
+        expected[135] ^= 0x80;
+
+        let mut batcher = KeccakBatcher::default().with_permutation(|_| {});
+        let output = batcher.hash(&message).unwrap();
+        assert_eq!(output, &expected[..32]);
+
+        let mut absorbed = KeccakBatcher::default();
+        absorbed.absorb(&message, &output).unwrap();
+        assert_eq!(batcher.transcript(), absorbed.transcript());
+
+        assert!(KeccakBatcher::default().hash(&message).is_err());
+    }
+
+    #[test]
+    fn hash_at_rate_minus_one() {
+        // The padding of a message leaving one byte in its block is the single byte 0x81.
+        let message = [3u8; KeccakBatcher::BLOCK_BYTES - 1];
+        let mut expected = [3u8; KeccakBatcher::BLOCK_BYTES];
+        expected[KeccakBatcher::BLOCK_BYTES - 1] = 0x81;
+
+        let mut batcher = KeccakBatcher::default().with_permutation(|_| {});
+        let output = batcher.hash(&message).unwrap();
+        assert_eq!(output, &expected[..32]);
+
+        let mut absorbed = KeccakBatcher::default();
+        absorbed.absorb(&message, &output).unwrap();
+        assert_eq!(batcher.transcript(), absorbed.transcript());
+        assert_eq!(batcher.block_count(), 1);
+    }
+
+    #[test]
+    fn transcript_grows_on_demand() {
+        let mut batcher = KeccakBatcher::init();
+        assert_eq!(batcher.input_transcript.capacity(), 0);
+        assert!(core::mem::size_of::<KeccakBatcher>() < 128);
+
+        batcher.write_keccak_entry(b"hello", &[1u8; 32]).unwrap();
+        let capacity = batcher.input_transcript.capacity();
+        assert!(capacity >= batcher.transcript().len());
+        assert!(capacity < KeccakBatcher::KECCAK_LIMIT);
+
+        // Filling the transcript grows it up to, but not past, the limit.
+        for _ in 0..KeccakBatcher::KECCAK_LIMIT / 1000 {
+            batcher
+                .write_keccak_entry(&[2u8; 1000], &[2u8; 32])
+                .unwrap();
+            assert!(batcher.input_transcript.capacity() <= KeccakBatcher::KECCAK_LIMIT);
+        }
+        assert_eq!(
+            batcher.input_transcript.capacity(),
+            KeccakBatcher::KECCAK_LIMIT
+        );
+
+        let mut batcher = KeccakBatcher::default();
+        assert!(batcher
+            .write_data(&[0u8; KeccakBatcher::KECCAK_LIMIT])
+            .is_err());
+        assert!(!batcher.has_data());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid keccak rate")]
+    fn invalid_rate() {
+        let _ = KeccakBatcher::default().with_rate(100);
+    }
+}
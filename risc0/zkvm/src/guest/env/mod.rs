@@ -24,6 +24,16 @@
 //! exiting the program explicitly. This can be achieved using the [pause] and
 //! [exit] functions.
 //!
+//! A guest cannot obtain its own image ID. The image ID is the digest of the
+//! program's initial memory image, which includes the program itself, so it
+//! cannot be embedded in the program, and the zkVM does not expose it to the
+//! guest at runtime. It does not need to be committed to the journal: the image
+//! ID is part of the [ReceiptClaim][crate::ReceiptClaim], and a verifier
+//! confirms which program produced a receipt by verifying it against the
+//! expected image ID. A value read from the host, such as an image ID passed
+//! as input, is not checked and only describes the running program if the
+//! verifier checks it separately.
+//!
 //! ## Proof Verification
 //!
 //! The zkVM supports verification of RISC Zero [receipts] in a guest program,
@@ -58,6 +68,15 @@
 //! descriptor is not recommended and may lead to unexpected behavior. A list of
 //! default file descriptors can be found in the [fileno] module.
 //!
+//! Data committed to the journal is hashed with SHA-256 as it is written, and
+//! this digest is part of the receipt claim. The digest function is fixed: the
+//! host, the prover, and [Receipt::verify][crate::Receipt::verify] all recompute
+//! the journal digest as the SHA-256 hash of the journal bytes, so it cannot be
+//! replaced with another hash function in the guest. To save cycles when the
+//! data is consumed by a verifier that uses a different hash, such as keccak on
+//! an EVM chain, commit the digest of the data in that hash function rather
+//! than the data itself, which keeps the SHA-256 input to a single block.
+//!
 //! ## Utility
 //!
 //! The zkVM provides utility functions to log messages to the debug console and
@@ -77,12 +96,13 @@ mod write;
 
 use alloc::{
     alloc::{alloc, Layout},
-    vec,
+    format, vec,
 };
 
 use anyhow::Result;
 use bytemuck::Pod;
 use core::cell::OnceCell;
+use risc0_binfmt::tagged_list;
 use risc0_zkvm_platform::{
     align_up, fileno,
     syscall::{
@@ -94,23 +114,37 @@ use risc0_zkvm_platform::{
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
+    serde::{Serializer, WordRead as _},
     sha::{
         rust_crypto::{Digest as _, Sha256},
-        Digest, Digestible,
+        Digest, Digestible, Sha256 as _, DIGEST_BYTES,
     },
     Assumptions, MaybePruned, Output,
 };
 
 #[cfg(feature = "unstable")]
-use self::batcher::KeccakBatcher;
+pub use self::batcher::KeccakBatcher;
 pub use self::{
     read::{FdReader, Read},
-    verify::{verify, verify_assumption, verify_integrity, VerifyIntegrityError},
-    write::{FdWriter, Write},
+    verify::{
+        verify, verify_and_commit, verify_assumption, verify_first_matching, verify_integrity,
+        VerifyIntegrityError,
+    },
+    write::{FdWriter, TeeWriter, Write, MAX_WRITE_CHUNK_BYTES},
 };
 
 static mut HASHER: OnceCell<Sha256> = OnceCell::new();
 
+/// Number of bytes written to the journal, and hashed by [HASHER].
+static mut JOURNAL_LEN: usize = 0;
+
+/// Total number of bytes hashed by [HASHER] since the guest began, including
+/// journals committed before a pause.
+static mut JOURNAL_BYTES_HASHED: u64 = 0;
+
+/// Number of times the guest has paused and resumed, used by [Checkpoint].
+static mut PAUSE_COUNT: u32 = 0;
+
 /// Digest of the running list of [Assumptions], generated by the [self::verify] and
 /// [self::verify_integrity] calls made by the guest.
 static mut ASSUMPTIONS_DIGEST: MaybePruned<Assumptions> = MaybePruned::Pruned(Digest::ZERO);
@@ -126,6 +160,21 @@ pub(crate) fn init() {
     unsafe {
         #[allow(static_mut_refs)]
         HASHER.set(Sha256::new()).unwrap();
+        JOURNAL_LEN = 0;
+    }
+    reseed_memory_entropy();
+}
+
+/// Refresh the memory image entropy with new random data from the host.
+///
+/// The entropy is seeded on guest start and upon resuming from a pause, which is
+/// sufficient to prevent information leakage through the post-state digest.
+/// Long-running guests that fork many times (e.g. via `run_unconstrained`) may
+/// call this to avoid any correlation between the values observed across
+/// forks. Only the value present when the guest halts or pauses contributes to
+/// the post-state digest.
+pub fn reseed_memory_entropy() {
+    unsafe {
         #[allow(static_mut_refs)]
         syscall::sys_rand(
             MEMORY_IMAGE_ENTROPY.as_mut_ptr(),
@@ -134,6 +183,27 @@ pub(crate) fn init() {
     }
 }
 
+/// Returns `true` if the memory image entropy was seeded by the host.
+///
+/// The entropy words are filled by the host via `sys_rand` on guest start and upon resuming from
+/// a pause. If they are all zero, the host most likely skipped the syscall and the memory image
+/// does not have the 128 bits of entropy needed to prevent leakage through the post-state digest.
+pub fn memory_entropy_seeded() -> bool {
+    #[allow(static_mut_refs)]
+    unsafe {
+        MEMORY_IMAGE_ENTROPY.iter().any(|word| *word != 0)
+    }
+}
+
+/// Abort the guest if the memory image entropy was not seeded by the host.
+///
+/// See [memory_entropy_seeded] for details.
+pub fn require_memory_entropy() {
+    if !memory_entropy_seeded() {
+        crate::guest::abort("memory image entropy was not seeded by the host");
+    }
+}
+
 /// Finalize execution
 pub(crate) fn finalize(halt: bool, user_exit: u8) {
     unsafe {
@@ -145,12 +215,7 @@ pub(crate) fn finalize(halt: bool, user_exit: u8) {
 
         #[allow(static_mut_refs)]
         let hasher = HASHER.take();
-        let journal_digest: Digest = hasher.unwrap().finalize().as_slice().try_into().unwrap();
-        #[allow(static_mut_refs)]
-        let output = Output {
-            journal: MaybePruned::Pruned(journal_digest),
-            assumptions: MaybePruned::Pruned(ASSUMPTIONS_DIGEST.digest()),
-        };
+        let output = build_output(hasher.unwrap());
         let output_words: [u32; 8] = output.digest().into();
 
         if halt {
@@ -161,6 +226,29 @@ pub(crate) fn finalize(halt: bool, user_exit: u8) {
     }
 }
 
+fn build_output(hasher: Sha256) -> Output {
+    let journal_digest: Digest = hasher.finalize().as_slice().try_into().unwrap();
+    #[allow(static_mut_refs)]
+    let assumptions_digest = unsafe { ASSUMPTIONS_DIGEST.digest() };
+    Output {
+        journal: MaybePruned::Pruned(journal_digest),
+        assumptions: MaybePruned::Pruned(assumptions_digest),
+    }
+}
+
+/// Return the [Output] the guest would produce if it halted now.
+///
+/// This is built from the journal committed so far and the assumptions added so
+/// far, in the same way as the output committed when the guest halts. The
+/// journal hasher is cloned, so the guest can keep committing afterwards, which
+/// will change the final output. This is useful to inspect the assumptions
+/// digest when debugging composition.
+pub fn current_output() -> Output {
+    #[allow(static_mut_refs)]
+    let hasher = unsafe { HASHER.get().unwrap().clone() };
+    build_output(hasher)
+}
+
 /// Terminate execution of the zkVM.
 ///
 /// Use an exit code of 0 to indicate success, and non-zero to indicate an error.
@@ -169,13 +257,211 @@ pub fn exit(exit_code: u8) -> ! {
     unreachable!();
 }
 
+/// Commit the given message to the journal and terminate execution of the zkVM.
+///
+/// Unlike [crate::guest::abort], this halts normally so a receipt can still be
+/// produced. The message is serialized with [self::commit], so it is covered by
+/// the journal digest and can be decoded by the verifier as a [String][alloc::string::String]
+/// following any data committed before this call.
+pub fn abort_with(exit_code: u8, message: &str) -> ! {
+    commit(&message);
+    exit(exit_code);
+}
+
+/// Commit a 32-bit status word to the journal and terminate execution of the zkVM.
+///
+/// The exit code of the zkVM is a single byte, so this commits the full status
+/// with [self::commit] as the last 4 bytes of the journal, and halts with an
+/// exit code of 0 if `status` is 0, or 1 otherwise. The verifier can then check
+/// the exit code for success and read the status from the end of the journal.
+/// As with [exit], a receipt with a non-zero exit code does not pass
+/// [Receipt::verify][crate::Receipt::verify], and its claim must be checked
+/// explicitly.
+pub fn exit_with_status(status: u32) -> ! {
+    commit(&status);
+    exit(u8::from(status != 0));
+}
+
+/// Terminate execution of the zkVM with the given exit code if a condition does not hold.
+///
+/// If the condition is false, the optional message is written to the host log
+/// with [log] and the guest halts using [exit]. Unlike `assert!`, this halts
+/// normally, so the failure is reported as a [Halted] exit code rather than a
+/// fault. An exit code of 0 halts as if the guest had succeeded, so a non-zero
+/// code should be used.
+///
+/// [Halted]: crate::ExitCode::Halted
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let balance: u64 = env::read();
+/// let amount: u64 = env::read();
+/// env::ensure!(amount <= balance, 2, "insufficient balance");
+/// env::ensure!(amount != 0, 3);
+/// ```
+#[doc(inline)]
+pub use crate::__guest_env_ensure as ensure;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __guest_env_ensure {
+    ($cond:expr, $exit_code:expr $(,)?) => {
+        if !$cond {
+            $crate::guest::env::exit($exit_code);
+        }
+    };
+    ($cond:expr, $exit_code:expr, $msg:expr $(,)?) => {
+        if !$cond {
+            $crate::guest::env::log($msg);
+            $crate::guest::env::exit($exit_code);
+        }
+    };
+}
+
+/// Maximum number of bytes of a panic message committed by [install_panic_committer].
+pub const MAX_PANIC_MESSAGE_BYTES: usize = 512;
+
+/// Exit code used when a panic is committed by [install_panic_committer].
+pub const PANIC_EXIT_CODE: u8 = 101;
+
+/// Set by [install_panic_committer] and cleared when a panic is committed, so a
+/// panic raised while committing falls back to the default panic behavior.
+#[cfg(any(feature = "std", target_os = "zkvm"))]
+static mut PANIC_COMMITTER: bool = false;
+
+/// Commit the message and location of any subsequent panic to the journal.
+///
+/// Once installed, a panic commits its message, truncated to
+/// [MAX_PANIC_MESSAGE_BYTES], followed by its location as an
+/// `Option<(String, u32)>` of the file, truncated to the same length, and line.
+/// Both are committed with [self::commit], and the guest then halts with
+/// [PANIC_EXIT_CODE]. This makes the reason for a panic available from the
+/// receipt, at the cost of producing a receipt for a failed execution.
+///
+/// Only the first panic is committed. A panic raised while committing is
+/// handled as if the committer had not been installed.
+pub fn install_panic_committer() {
+    #[cfg(any(feature = "std", target_os = "zkvm"))]
+    unsafe {
+        PANIC_COMMITTER = true
+    };
+    // The hook cannot be taken from within itself, since `std::panic::take_hook`
+    // panics while panicking. Returning from the hook instead continues with the
+    // default panic behavior.
+    #[cfg(feature = "std")]
+    std::panic::set_hook(alloc::boxed::Box::new(|info| {
+        if take_panic_committer() {
+            commit_panic(info, info.location());
+        }
+    }));
+}
+
+/// Returns `true` if the panic committer is installed, uninstalling it.
+#[cfg(any(feature = "std", target_os = "zkvm"))]
+pub(crate) fn take_panic_committer() -> bool {
+    unsafe {
+        let installed = PANIC_COMMITTER;
+        PANIC_COMMITTER = false;
+        installed
+    }
+}
+
+#[cfg(any(feature = "std", target_os = "zkvm"))]
+pub(crate) fn commit_panic(
+    panic_info: &dyn core::fmt::Display,
+    location: Option<&core::panic::Location>,
+) -> ! {
+    let msg = alloc::format!("{panic_info}");
+    commit(&(
+        truncate_panic_str(&msg),
+        location.map(|loc| (truncate_panic_str(loc.file()), loc.line())),
+    ));
+    exit(PANIC_EXIT_CODE)
+}
+
+#[cfg(any(feature = "std", target_os = "zkvm"))]
+fn truncate_panic_str(s: &str) -> &str {
+    let mut len = s.len().min(MAX_PANIC_MESSAGE_BYTES);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    &s[..len]
+}
+
 /// Pause the execution of the zkVM.
 ///
 /// Execution may be continued at a later time.
 /// Use an exit code of 0 to indicate success, and non-zero to indicate an error.
+///
+/// The journal committed so far is finalized into the claim of the paused
+/// execution. When execution resumes, the guest starts a new, empty journal:
+/// data committed after the pause is not appended to the journal from before
+/// it, and the journal digest is computed from scratch. The assumptions added
+/// before the pause are kept. Use [journal_is_fresh] to check whether anything
+/// has been committed since the guest started or last resumed.
 pub fn pause(exit_code: u8) {
     finalize(false, exit_code);
     init();
+    unsafe { PAUSE_COUNT += 1 };
+}
+
+/// A record of the progress of the guest, returned by [checkpoint].
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint {
+    pause_count: u32,
+    journal_position: usize,
+    cycle_count: u64,
+}
+
+impl Checkpoint {
+    /// The [journal_position] when the checkpoint was taken.
+    pub fn journal_position(&self) -> usize {
+        self.journal_position
+    }
+
+    /// The [cycle_count] when the checkpoint was taken.
+    pub fn cycle_count(&self) -> u64 {
+        self.cycle_count
+    }
+
+    /// Returns `true` if the guest has paused and resumed since the checkpoint
+    /// was taken.
+    ///
+    /// If so, the journal has restarted, and data committed before the pause is
+    /// part of the claim of an earlier receipt. The [journal_position] of the
+    /// checkpoint no longer refers to the current journal.
+    pub fn resumed_since(&self) -> bool {
+        unsafe { PAUSE_COUNT != self.pause_count }
+    }
+}
+
+/// Flush buffered output and record the progress of the guest.
+///
+/// Unlike [pause], this does not finalize the journal or end execution, and has
+/// no effect on the receipt: the guest keeps running in the same segment, and
+/// data committed afterwards is appended to the same journal. Output written
+/// with the `std::io` handles of the guest, if any, is flushed to the host so
+/// that the host has seen everything written before the checkpoint.
+///
+/// The returned [Checkpoint] records the journal position and cycle count, and
+/// can later be used to check whether the guest has been paused and resumed in
+/// the meantime, i.e. whether the work done since the checkpoint is covered by
+/// the same journal.
+pub fn checkpoint() -> Checkpoint {
+    #[cfg(feature = "std")]
+    {
+        use std::io::Write as _;
+        let _ = std::io::stdout().flush();
+        let _ = std::io::stderr().flush();
+    }
+    Checkpoint {
+        pause_count: unsafe { PAUSE_COUNT },
+        journal_position: journal_position(),
+        cycle_count: cycle_count(),
+    }
 }
 
 /// Exchange data with the host.
@@ -199,16 +485,35 @@ pub fn syscall(syscall: SyscallName, to_host: &[u8], from_host: &mut [u32]) -> s
 ///
 /// On the host side, implement SliceIo to provide a handler for this call.
 ///
+/// Panics if the number of bytes returned by the host is not a multiple of the
+/// size of `U`. Use [try_send_recv_slice] to handle this case.
+///
 /// NOTE: This method never frees up the buffer memory storing the host's response.
 pub fn send_recv_slice<T: Pod, U: Pod>(syscall_name: SyscallName, to_host: &[T]) -> &'static [U] {
+    try_send_recv_slice(syscall_name, to_host).unwrap()
+}
+
+/// Exchanges slices of plain old data with the host, checking the response length.
+///
+/// This is the same as [send_recv_slice], but returns an error if the number of
+/// bytes returned by the host is not a multiple of the size of `U`, instead of
+/// panicking. This catches the host and guest disagreeing on the element type.
+///
+/// NOTE: This method never frees up the buffer memory storing the host's response.
+pub fn try_send_recv_slice<T: Pod, U: Pod>(
+    syscall_name: SyscallName,
+    to_host: &[T],
+) -> Result<&'static [U], bytemuck::PodCastError> {
     let syscall::Return(nbytes, _) = syscall(syscall_name, bytemuck::cast_slice(to_host), &mut []);
     let nwords = align_up(nbytes as usize, WORD_SIZE) / WORD_SIZE;
     let from_host_buf = unsafe {
-        let layout = Layout::from_size_align(nwords * WORD_SIZE, WORD_SIZE).unwrap();
+        let align = core::cmp::max(WORD_SIZE, core::mem::align_of::<U>());
+        let layout = Layout::from_size_align(nwords * WORD_SIZE, align).unwrap();
         core::slice::from_raw_parts_mut(alloc(layout) as *mut u32, nwords)
     };
     syscall(syscall_name, &[], from_host_buf);
-    &bytemuck::cast_slice(from_host_buf)[..nbytes as usize / core::mem::size_of::<U>()]
+    let from_host: &'static [u8] = bytemuck::cast_slice(from_host_buf);
+    bytemuck::try_cast_slice(&from_host[..nbytes as usize])
 }
 
 /// Read private data from the STDIN of the zkVM and deserializes it.
@@ -236,6 +541,50 @@ pub fn read<T: DeserializeOwned>() -> T {
     stdin().read()
 }
 
+/// Read an optional value from the STDIN of the zkVM.
+///
+/// The host sends a discriminant word, `0` for [None] or `1` for [Some],
+/// followed by the value serialized as in [read] when present. This is the
+/// layout of an [Option] written with
+/// [ExecutorEnvBuilder::write](crate::ExecutorEnvBuilder::write), so the host
+/// can produce it directly. Unlike [read], an invalid discriminant or a short
+/// read is returned as an error instead of causing a panic.
+pub fn read_option<T: DeserializeOwned>() -> Result<Option<T>, crate::serde::Error> {
+    match read_discriminant()? {
+        0 => Ok(None),
+        1 => try_read().map(Some),
+        _ => Err(crate::serde::Error::DeserializeBadOption),
+    }
+}
+
+/// Read a [Result] from the STDIN of the zkVM.
+///
+/// The host sends a discriminant word, `0` for [Ok] or `1` for [Err], followed
+/// by the value or error serialized as in [read]. This is the layout of a
+/// [Result] written with
+/// [ExecutorEnvBuilder::write](crate::ExecutorEnvBuilder::write). An invalid
+/// discriminant or a short read is returned as an error.
+pub fn read_result<T: DeserializeOwned, E: DeserializeOwned>(
+) -> Result<Result<T, E>, crate::serde::Error> {
+    match read_discriminant()? {
+        0 => try_read().map(Ok),
+        1 => try_read().map(Err),
+        d => Err(crate::serde::Error::Custom(format!(
+            "invalid Result discriminant: {d}"
+        ))),
+    }
+}
+
+fn read_discriminant() -> Result<u32, crate::serde::Error> {
+    let mut discriminant = 0u32;
+    stdin().read_words(core::slice::from_mut(&mut discriminant))?;
+    Ok(discriminant)
+}
+
+fn try_read<T: DeserializeOwned>() -> Result<T, crate::serde::Error> {
+    T::deserialize(&mut crate::serde::Deserializer::new(stdin()))
+}
+
 /// Read a slice from the STDIN of the zkVM.
 ///
 /// This function reads a slice of [plain old data][bytemuck::Pod], not
@@ -266,6 +615,102 @@ pub fn read_slice<T: Pod>(slice: &mut [T]) {
     stdin().read_slice(slice)
 }
 
+/// Read a fixed-size array of [plain old data][bytemuck::Pod] from the STDIN
+/// of the zkVM.
+///
+/// Exactly `N` elements are read, with no length header, into an array on the
+/// stack, so no heap allocation is made. This is the counterpart of
+/// [commit_array], and of writing the array with `ExecutorEnvBuilder::write_slice`
+/// on the host.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let words: [u32; 8] = env::read_array();
+/// ```
+pub fn read_array<T: Pod, const N: usize>() -> [T; N] {
+    let mut array = [T::zeroed(); N];
+    read_slice(&mut array);
+    array
+}
+
+/// Read a slice from the STDIN of the zkVM, returning an error if the host
+/// does not supply enough data to fill it.
+///
+/// Unlike [read_slice], which panics when the input ends early, this lets the
+/// guest handle a host that under-fills a fixed-size input. Note that surplus
+/// input cannot be detected here; it is left on STDIN for subsequent reads.
+/// When an error is returned, the slice may have been partially filled.
+pub fn read_slice_exact<T: Pod>(slice: &mut [T]) -> Result<(), crate::serde::Error> {
+    let mut reader = stdin();
+    if let Ok(words) = bytemuck::try_cast_slice_mut(slice) {
+        reader.read_words(words)
+    } else {
+        let bytes = bytemuck::cast_slice_mut(slice);
+        if reader.read_bytes_all(bytes) != bytes.len() {
+            return Err(crate::serde::Error::DeserializeUnexpectedEnd);
+        }
+        Ok(())
+    }
+}
+
+/// Read a magic header from the STDIN of the zkVM and check that it matches
+/// `expected`.
+///
+/// Exactly `expected.len()` bytes are read, so on a mismatch the rest of the
+/// input is left unread and the guest can reject a malformed stream before
+/// interpreting any of it. Returns an error if the header does not match or
+/// the input ends early.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// env::expect_magic(b"RZ01").expect("unsupported input format");
+/// ```
+pub fn expect_magic(expected: &[u8]) -> Result<()> {
+    let mut magic = vec![0u8; expected.len()];
+    read_slice_exact(&mut magic)
+        .map_err(|err| anyhow::anyhow!("failed to read magic header: {err}"))?;
+    if magic != expected {
+        anyhow::bail!("unexpected magic header: expected {expected:02x?}, got {magic:02x?}");
+    }
+    Ok(())
+}
+
+/// Read the STDIN of the zkVM in pages of `PAGE` bytes, calling `f` on each one.
+///
+/// A single buffer on the stack is reused for every page, so large inputs can
+/// be processed with bounded memory and one syscall per page. Reading stops
+/// when `f` returns `false` or the end of input is reached. The final page may
+/// be shorter than `PAGE` bytes, but is never empty.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let mut total = 0u64;
+/// env::read_pages::<1024>(|page| {
+///     total += page.iter().map(|&b| b as u64).sum::<u64>();
+///     true
+/// });
+/// ```
+pub fn read_pages<const PAGE: usize>(mut f: impl FnMut(&[u8]) -> bool) {
+    assert!(PAGE > 0, "page size must be non-zero");
+    let mut page = [0u8; PAGE];
+    let mut reader = stdin();
+    loop {
+        let len = reader.read_bytes_all(&mut page);
+        if len == 0 || !f(&page[..len]) || len < PAGE {
+            break;
+        }
+    }
+}
+
 /// Serialize the given data and write it to the STDOUT of the zkVM.
 ///
 /// This is available to the host as the private output on the prover.
@@ -293,7 +738,15 @@ pub fn read_slice<T: Pod>(slice: &mut [T]) {
 /// [example page]: https://dev.risczero.com/api/zkvm/examples
 /// [I/O documentation]: https://dev.risczero.com/api/zkvm/tutorials/io
 pub fn write<T: Serialize>(data: &T) {
-    stdout().write(data)
+    try_write(data).unwrap()
+}
+
+/// Serialize the given data and write it to the STDOUT of the zkVM, returning
+/// any serialization error.
+///
+/// This is the fallible version of [write()].
+pub fn try_write<T: Serialize>(data: &T) -> Result<(), crate::serde::Error> {
+    data.serialize(&mut Serializer::new(stdout()))
 }
 
 /// Write the given slice to the STDOUT of the zkVM.
@@ -324,7 +777,30 @@ pub fn write<T: Serialize>(data: &T) {
 /// [I/O documentation]: https://dev.risczero.com/api/zkvm/tutorials/io
 /// [instructions on guest optimization]: https://dev.risczero.com/api/zkvm/optimization#when-reading-data-as-raw-bytes-use-envread_slice
 pub fn write_slice<T: Pod>(slice: &[T]) {
-    stdout().write_slice(slice);
+    write_slice_to(fileno::STDOUT, slice);
+}
+
+/// Write the given slice to the given file descriptor of the zkVM.
+///
+/// This is the same as [write_slice], except the data is written to `fd`
+/// rather than STDOUT, e.g. to emit raw diagnostic data on STDERR. Data written
+/// to [fileno::JOURNAL] is committed as with [commit_slice].
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+/// use risc0_zkvm_platform::fileno;
+///
+/// let slice = [1u32, 2, 3, 4];
+/// env::write_slice_to(fileno::STDERR, &slice);
+/// ```
+pub fn write_slice_to<T: Pod>(fd: u32, slice: &[T]) {
+    if fd == fileno::JOURNAL {
+        journal().write_slice(slice);
+    } else {
+        FdWriter::new(fd, |_| {}).write_slice(slice);
+    }
 }
 
 /// Serialize the given data and commit it to the journal.
@@ -353,7 +829,90 @@ pub fn write_slice<T: Pod>(slice: &[T]) {
 /// [example page]: https://dev.risczero.com/api/zkvm/examples
 /// [I/O documentation]: https://dev.risczero.com/api/zkvm/tutorials/io
 pub fn commit<T: Serialize>(data: &T) {
-    journal().write(data)
+    try_commit(data).unwrap()
+}
+
+/// Serialize the given data and commit it to the journal, returning any
+/// serialization error.
+///
+/// This is the fallible version of [self::commit]. Note that data serialized
+/// before the error occurred has already been committed.
+pub fn try_commit<T: Serialize>(data: &T) -> Result<(), crate::serde::Error> {
+    data.serialize(&mut Serializer::new(journal()))
+}
+
+/// Serialize the given data in canonical form and commit it to the journal.
+///
+/// Map entries are written sorted by their serialized keys, so the journal is
+/// byte-stable for equal values even when a map's iteration order is not, e.g.
+/// for a `HashMap`. Otherwise the encoding is the same as [self::commit], so
+/// the host decodes it the same way. See
+/// [Serializer::new_canonical][crate::serde::Serializer::new_canonical] for the
+/// canonicalization rules. Each map is buffered in guest memory before it is
+/// committed.
+pub fn commit_canonical<T: Serialize>(data: &T) {
+    data.serialize(&mut Serializer::new_canonical(journal()))
+        .unwrap()
+}
+
+/// Serialize each item of the iterator and commit it to the journal, without
+/// collecting the items first.
+///
+/// The journal receives the number of items as a `u32` word, followed by each
+/// item serialized as in [self::commit]. This is the same layout as committing
+/// a [Vec][alloc::vec::Vec] of the items, so the host can decode it with
+/// [Journal::read_seq][crate::Journal::read_seq]. The iterator must report its
+/// exact length up front, since the count is written before the items. Panics,
+/// before committing anything, if the length does not fit in a `u32`, and
+/// panics if the iterator yields a different number of items than its length.
+pub fn commit_iter<T: Serialize>(iter: impl ExactSizeIterator<Item = T>) {
+    let len = iter.len();
+    commit(&u32::try_from(len).expect("iterator length exceeds u32::MAX"));
+    let mut count = 0;
+    for item in iter {
+        commit(&item);
+        count += 1;
+    }
+    assert_eq!(count, len, "iterator yielded {count} items, expected {len}");
+}
+
+/// Serialize the given data as CBOR and commit it to the journal.
+///
+/// Unlike [self::commit], CBOR is self-describing, so the public output can be
+/// decoded by verifiers written in any language with a standard CBOR library.
+/// The journal digest covers the CBOR bytes exactly as committed.
+///
+/// CBOR costs more cycles than the native codec. Every item carries a type
+/// header, struct field names are written as strings, and the encoding is
+/// byte-oriented, where the native codec writes whole words. The larger output
+/// also takes more cycles to hash into the journal digest. Prefer
+/// [self::commit] when the journal is only decoded by Rust code.
+///
+/// Requires the `cbor` feature. Panics if the data cannot be encoded.
+#[cfg(feature = "cbor")]
+pub fn commit_cbor<T: Serialize>(data: &T) {
+    let mut buf = alloc::vec::Vec::new();
+    ciborium::into_writer(data, &mut buf).expect("failed to encode data as CBOR");
+    commit_slice(&buf);
+}
+
+/// Serialize the given data and commit it to the journal with a version prefix.
+///
+/// The journal receives the version as 2 little-endian bytes, followed by the
+/// data serialized as in [self::commit]. On the host, use
+/// [Journal::read_versioned][crate::Journal::read_versioned] to decode it.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// env::commit_versioned(1, &42u64);
+/// ```
+pub fn commit_versioned<T: Serialize>(version: u16, data: &T) {
+    let mut journal = journal();
+    journal.write_slice(&version.to_le_bytes());
+    journal.write(data);
 }
 
 /// Commit the given slice to the journal.
@@ -386,6 +945,223 @@ pub fn commit_slice<T: Pod>(slice: &[T]) {
     journal().write_slice(slice);
 }
 
+/// Commit a fixed-size array of [plain old data][bytemuck::Pod] to the
+/// journal.
+///
+/// The `N` elements are committed as raw bytes with no length header, as with
+/// [commit_slice], so the journal is `N * size_of::<T>()` bytes long. The
+/// length is part of the type, so the guest and the host only need to agree on
+/// `T` and `N` to decode it.
+pub fn commit_array<T: Pod, const N: usize>(array: &[T; N]) {
+    commit_slice(array.as_slice());
+}
+
+/// Commit a `u32` to the journal as 4 raw big-endian bytes.
+///
+/// Unlike [self::commit], which writes a little-endian word, this writes the
+/// integer in the byte order used by EVM verifiers with no framing or padding.
+/// On the host, use [Journal::read_u32_be][crate::Journal::read_u32_be] to decode it.
+pub fn commit_u32_be(x: u32) {
+    commit_slice(&x.to_be_bytes());
+}
+
+/// Commit a `u64` to the journal as 8 raw big-endian bytes.
+///
+/// See [commit_u32_be]. On the host, use
+/// [Journal::read_u64_be][crate::Journal::read_u64_be] to decode it.
+pub fn commit_u64_be(x: u64) {
+    commit_slice(&x.to_be_bytes());
+}
+
+/// Commit a `u128` to the journal as 16 raw big-endian bytes.
+///
+/// See [commit_u32_be]. On the host, use
+/// [Journal::read_u128_be][crate::Journal::read_u128_be] to decode it.
+pub fn commit_u128_be(x: u128) {
+    commit_slice(&x.to_be_bytes());
+}
+
+/// Commit a bitmask recording which optional inputs were present.
+///
+/// Bit `i` of `mask`, counting from the least significant bit, is set if the
+/// `i`-th optional input read by the guest, e.g. with [read_option], was
+/// present. The mask is committed as 8 raw little-endian bytes, so bit `i` is
+/// bit `i % 8` of byte `i / 8`. On the host, use
+/// [Journal::read_presence_mask][crate::Journal::read_presence_mask] to decode
+/// it.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let fee: Option<u64> = env::read_option().unwrap();
+/// let memo: Option<String> = env::read_option().unwrap();
+/// env::commit_presence_mask(u64::from(fee.is_some()) | u64::from(memo.is_some()) << 1);
+/// ```
+pub fn commit_presence_mask(mask: u64) {
+    commit_slice(&mask.to_le_bytes());
+}
+
+/// Commit a list of digests to the journal as fixed-size records.
+///
+/// The journal receives the number of digests as a 4-byte little-endian
+/// length, followed by the 32 bytes of each digest back-to-back. This layout
+/// is more compact than [self::commit], and is easy to decode on-chain. On the
+/// host, use [Journal::read_digests][crate::Journal::read_digests] to decode it.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{guest::env, sha::Digest};
+///
+/// let pre_state: Digest = env::read();
+/// let post_state: Digest = env::read();
+/// env::commit_digests(&[pre_state, post_state]);
+/// ```
+pub fn commit_digests(digests: &[Digest]) {
+    commit_slice(&[digests.len() as u32]);
+    commit_slice(digests);
+}
+
+/// Tag used by [aggregate_journal_digests] to domain separate the aggregation
+/// digest.
+pub const JOURNAL_AGGREGATION_TAG: &str = "risc0.JournalAggregation";
+
+/// Fold the journal digests of child receipts into one digest, commit it to
+/// the journal, and return it.
+///
+/// This gives a guest that verifies several child receipts, e.g. with
+/// [verify], a canonical commitment to all of their journals. The digests are
+/// hashed in order as a tagged list with the tag [JOURNAL_AGGREGATION_TAG],
+/// using the same construction as the assumptions list of a
+/// [ReceiptClaim][crate::ReceiptClaim]. Starting from the zero digest, each
+/// digest is folded in from the last to the first as:
+///
+/// ```text
+/// acc = SHA-256(SHA-256(JOURNAL_AGGREGATION_TAG) || digest || acc || 2u16)
+/// ```
+///
+/// where `2u16` is two little-endian bytes. An empty list aggregates to the
+/// zero digest. The 32 bytes of the result are committed to the journal with
+/// [commit_slice]. Off-chain, the result can be recomputed with
+/// `risc0_binfmt::tagged_list` and the same tag.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{guest::env, sha::Digest};
+///
+/// let journal_digests: Vec<Digest> = env::read();
+/// let aggregate = env::aggregate_journal_digests(&journal_digests);
+/// ```
+pub fn aggregate_journal_digests(digests: &[Digest]) -> Digest {
+    let digest = tagged_list::<crate::sha::Impl>(JOURNAL_AGGREGATION_TAG, digests);
+    commit_slice(digest.as_bytes());
+    digest
+}
+
+/// Serialize the given data, commit it to the journal, and return the SHA-256
+/// digest of the committed bytes.
+///
+/// The journal contents are identical to calling [self::commit]. The returned
+/// digest covers only the serialization of `data`, not the whole journal.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let output: Vec<u32> = vec![1, 2, 3];
+/// let digest = env::commit_returning_digest(&output);
+/// ```
+pub fn commit_returning_digest<T: Serialize>(data: &T) -> Digest {
+    let words = crate::serde::to_vec(data).unwrap();
+    journal().write_slice(&words);
+    bytemuck::cast_slice::<_, u8>(&words).digest()
+}
+
+/// Serialize the given data once and write it to both the STDOUT and the journal.
+///
+/// The bytes written to each file descriptor are identical to calling [write()]
+/// followed by [self::commit], but the serialization cost is only paid once.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let result: u64 = 42;
+/// env::write_and_commit(&result);
+/// ```
+pub fn write_and_commit<T: Serialize>(data: &T) {
+    let words = crate::serde::to_vec(data).unwrap();
+    stdout().write_slice(&words);
+    journal().write_slice(&words);
+}
+
+/// Convention used to combine a node with its sibling in a Merkle path.
+#[derive(Clone, Copy, Debug)]
+pub enum MerklePathOrder {
+    /// Each pair is hashed with the lesser digest (by byte value) first.
+    Sorted,
+
+    /// Each pair is ordered by the bit of the leaf index at the current level.
+    ///
+    /// A zero bit means the current node is on the left.
+    Indexed(u64),
+}
+
+/// Verify a Merkle inclusion proof and commit the expected root to the journal.
+///
+/// The root is recomputed from `leaf` and each digest in `path`, from the leaf
+/// level upwards. Leaves and internal nodes are hashed with distinct prefixes,
+/// as in RFC 6962, so that a leaf can never be confused with an internal node:
+///
+/// * a leaf is hashed as `SHA-256(0x00 || leaf)`.
+/// * a pair of nodes is hashed as `SHA-256(0x01 || left || right)`, ordered
+///   according to `order`.
+///
+/// The pair `(root, matched)` is committed with [self::commit], where `matched`
+/// is whether the recomputed root equals `root`, so the journal distinguishes a
+/// valid proof from an invalid one. Returns `matched`.
+pub fn commit_merkle_root(
+    leaf: &[u8],
+    path: &[Digest],
+    root: Digest,
+    order: MerklePathOrder,
+) -> bool {
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_LEAF_PREFIX]);
+    hasher.update(leaf);
+    let mut node: Digest = hasher.finalize().as_slice().try_into().unwrap();
+    for (level, sibling) in path.iter().enumerate() {
+        let node_is_left = match order {
+            MerklePathOrder::Sorted => node.as_bytes() <= sibling.as_bytes(),
+            MerklePathOrder::Indexed(index) => (index >> level) & 1 == 0,
+        };
+        let (left, right) = if node_is_left {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        let mut pair = [0u8; 1 + 2 * DIGEST_BYTES];
+        pair[0] = MERKLE_NODE_PREFIX;
+        pair[1..][..DIGEST_BYTES].copy_from_slice(left.as_bytes());
+        pair[1 + DIGEST_BYTES..].copy_from_slice(right.as_bytes());
+        node = *crate::sha::Impl::hash_bytes(&pair);
+    }
+    let matched = node == root;
+    commit(&(root, matched));
+    matched
+}
+
+/// Prefix of a leaf hashed by [commit_merkle_root].
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+
+/// Prefix of a pair of nodes hashed by [commit_merkle_root].
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
 /// Return the number of processor cycles that have occurred since the guest
 /// began.
 ///
@@ -394,6 +1170,57 @@ pub fn cycle_count() -> u64 {
     sys_cycle_count()
 }
 
+/// Commit a cycle count marker to the journal.
+///
+/// The marker records `label_id`, which identifies a point in the guest chosen
+/// by the caller, and the current [cycle_count]. It is committed as 12 raw
+/// bytes: `label_id` as a little-endian `u32`, followed by the cycle count as a
+/// little-endian `u64`. On the host, use
+/// [Journal::read_cycle_marker][crate::Journal::read_cycle_marker] to decode
+/// it.
+///
+/// WARNING: The cycle count is provided by the host and is not checked by the
+/// zkVM circuit. A marker shows what the host reported to this execution, so it
+/// is advisory and must not be relied on as a proven measure of cost.
+pub fn commit_cycle_marker(label_id: u32) {
+    let cycles = cycle_count();
+    let mut marker = [0u8; 12];
+    marker[..4].copy_from_slice(&label_id.to_le_bytes());
+    marker[4..].copy_from_slice(&cycles.to_le_bytes());
+    commit_slice(&marker);
+}
+
+/// Return the number of syscalls issued to the host since the guest began.
+///
+/// Each host I/O operation, such as a [read_slice] or [commit_slice], issues
+/// one or more syscalls, so this can be used to find code that makes many
+/// small reads or writes. The call to this function is not included.
+///
+/// WARNING: The syscall count is provided by the host and is not checked by the zkVM circuit.
+pub fn syscall_count() -> u64 {
+    syscall::sys_syscall_count()
+}
+
+/// Exit code used by [with_cycle_budget] when the cycle budget is exceeded.
+pub const CYCLE_BUDGET_EXIT_CODE: u8 = 102;
+
+/// Run the given function, then exit if the cycle count exceeds `limit`.
+///
+/// After `f` returns, the [cycle_count] is compared to `limit`. If it is
+/// larger, the guest halts with [CYCLE_BUDGET_EXIT_CODE] so that it fails
+/// fast with a known reason, rather than producing an oversized session.
+/// Otherwise the result of `f` is returned.
+///
+/// Because the cycle count is provided by the host, this check is advisory and
+/// not a soundness mechanism. A dishonest host can report any cycle count.
+pub fn with_cycle_budget<T>(limit: u64, f: impl FnOnce() -> T) -> T {
+    let result = f();
+    if cycle_count() > limit {
+        exit(CYCLE_BUDGET_EXIT_CODE);
+    }
+    result
+}
+
 /// Print a message to the debug console.
 pub fn log(msg: &str) {
     let msg = msg.as_bytes();
@@ -412,21 +1239,94 @@ pub fn stderr() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
     FdWriter::new(fileno::STDERR, |_| {})
 }
 
+/// Return a writer that writes to both `first` and `second`.
+///
+/// For example, `env::tee(env::stdout(), env::journal())` writes the same bytes
+/// to STDOUT and commits them to the journal in one pass, serializing values
+/// only once. See [TeeWriter].
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env::{self, Write};
+///
+/// let mut tee = env::tee(env::stdout(), env::journal());
+/// tee.write(&42u32);
+/// tee.write_slice(b"hello");
+/// ```
+pub fn tee<F: Fn(&[u8]), G: Fn(&[u8])>(first: FdWriter<F>, second: FdWriter<G>) -> TeeWriter<F, G> {
+    TeeWriter::new(first, second)
+}
+
 /// Return a writer for the JOURNAL.
 pub fn journal() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
     FdWriter::new(fileno::JOURNAL, |bytes| {
         #[allow(static_mut_refs)]
         unsafe {
-            HASHER.get_mut().unwrap_unchecked().update(bytes)
+            HASHER.get_mut().unwrap_unchecked().update(bytes);
+            JOURNAL_LEN += bytes.len();
+            JOURNAL_BYTES_HASHED += bytes.len() as u64;
         };
     })
 }
 
+/// Return the number of bytes committed to the journal so far.
+///
+/// The count restarts from zero when the guest resumes after a [pause].
+///
+/// This is the byte offset at which the next committed data will start. A guest
+/// can record the offset of a field before committing it, and then commit the
+/// offset as well, allowing a verifier to locate the field within the journal.
+pub fn journal_position() -> usize {
+    unsafe { JOURNAL_LEN }
+}
+
+/// Returns `true` if nothing has been committed to the journal yet.
+///
+/// A new journal is started when the guest starts and each time it resumes
+/// after a [pause], so this tells a resumed guest whether it is at the start of
+/// a new journal. See [pause] for how the journal is handled across a pause.
+pub fn journal_is_fresh() -> bool {
+    journal_position() == 0
+}
+
+/// Return the total number of bytes hashed into the journal digest since the
+/// guest began.
+///
+/// Every byte committed to the journal is hashed with SHA-256 as it is written,
+/// so commit-heavy guests spend cycles on hashing that are attributed to the
+/// commit calls rather than to the computation. Hashing costs roughly one
+/// SHA-256 compression per 64 bytes, so this can be used to estimate that cost,
+/// e.g. to decide whether to commit a digest of large data instead of the data
+/// itself. Unlike [journal_position], this count is not reset by a [pause].
+///
+/// This count is advisory and intended for profiling. See RISC Zero's
+/// [guest optimization guide] for more on reducing cycle counts.
+///
+/// [guest optimization guide]: https://dev.risczero.com/api/zkvm/optimization
+pub fn journal_bytes_hashed() -> u64 {
+    unsafe { JOURNAL_BYTES_HASHED }
+}
+
 /// Return a reader for the standard input
 pub fn stdin() -> FdReader {
     FdReader::new(fileno::STDIN)
 }
 
+/// Return the number of bytes remaining on stdin, if known.
+///
+/// The host knows the length of stdin when it was written with the `write`
+/// methods of `ExecutorEnvBuilder`. For other sources, such as a reader passed
+/// to `ExecutorEnvBuilder::stdin`, this returns `None`. This can be used to
+/// allocate a buffer of the right size before reading, or to report an error
+/// when less input is provided than expected.
+///
+/// WARNING: The length is provided by the host and is not checked by the zkVM
+/// circuit. It is advisory, and the guest must still handle short reads.
+pub fn stdin_peek_len() -> Option<usize> {
+    usize::try_from(syscall::sys_stdin_len()).ok()
+}
+
 /// Read the input digest from the input commitment.
 pub fn input_digest() -> Digest {
     Digest::new([
@@ -445,12 +1345,27 @@ pub fn input_digest() -> Digest {
 ///
 /// This does not provide any guarantees about the soundness of the execution,
 /// but can potentially be executed faster.
+///
+/// The function is run in a child process created with `sys_fork`, so any
+/// changes it makes to memory are discarded when it returns.
+///
+/// If the host does not support forking (see `ExecutorEnvBuilder::disable_fork`),
+/// `sys_fork` returns `-1` and the function is instead run inline. In that case
+/// it is part of the proven execution, and its changes to memory persist.
+///
+/// Calls may be nested: `f` can itself call [run_unconstrained], in which case
+/// the inner function runs in a child of the child process. Each child runs to
+/// completion and exits before its parent continues, so the outermost caller
+/// resumes after all nested children have finished. The host limits the depth
+/// of nesting, and running past that limit is an error.
 #[stability::unstable]
 pub fn run_unconstrained(f: impl FnOnce()) {
     let pid = sys_fork();
     if pid == 0 {
         f();
         sys_exit(0)
+    } else if pid < 0 {
+        f();
     }
 }
 
@@ -459,28 +1374,250 @@ pub fn run_unconstrained(f: impl FnOnce()) {
 /// A frame contains a length header along with the payload. Reading a frame can
 /// be more efficient than deserializing a message on-demand. On-demand
 /// deserialization can cause many syscalls, whereas a frame will only have two.
+///
+/// The length header is a little-endian `u32`, as written by the host's
+/// `ExecutorEnvBuilder::write_frame`. Use [read_frame_be] for a big-endian
+/// length header.
+///
+/// The length header is not checked, so the host can force an allocation of up
+/// to 4 GiB. Use [read_frame_capped] to bound the frame length.
 #[stability::unstable]
 pub fn read_frame() -> alloc::vec::Vec<u8> {
-    let mut len: u32 = 0;
-    read_slice(core::slice::from_mut(&mut len));
-    let mut bytes = vec![0u8; len as usize];
+    let len = read_frame_len();
+    let mut bytes = vec![0u8; len];
+    read_slice(&mut bytes);
+    bytes
+}
+
+/// Read a length-prefixed UTF-8 string from the host via `stdin`.
+///
+/// The string is sent as a frame, i.e. a little-endian `u32` byte length
+/// followed by that many bytes of UTF-8 with no padding, as written by the
+/// host's `ExecutorEnvBuilder::write_frame`. This avoids the per-character work
+/// of deserializing a [String][alloc::string::String] with [read()], and
+/// returns an error rather than panicking if the bytes are not valid UTF-8.
+#[stability::unstable]
+pub fn read_string() -> Result<alloc::string::String, core::str::Utf8Error> {
+    alloc::string::String::from_utf8(read_frame()).map_err(|err| err.utf8_error())
+}
+
+/// Read a record terminated by `delim` from the host via `stdin`.
+///
+/// Returns the bytes of the record without the delimiter. Consecutive
+/// delimiters yield an empty record. At the end of input, a trailing record
+/// with no delimiter is returned as is, and once no bytes remain this returns
+/// `Ok(None)`. This supports line-oriented input, e.g. with `b'\n'` as the
+/// delimiter, without pulling in `BufRead`.
+///
+/// If a record is longer than `max_record` bytes, the rest of the record is
+/// skipped up to and including its delimiter and [FrameError::RecordTooLarge]
+/// is returned, so the next call reads the following record.
+///
+/// Bytes are read one at a time so that nothing past the delimiter is consumed,
+/// which keeps the remaining input available to other reads from `stdin`. This
+/// costs one `sys_read` syscall per byte, which is far more expensive in cycles
+/// than reading a frame, where the payload is read with a single syscall.
+/// Prefer [read_frame] when the host can send the length up front.
+#[stability::unstable]
+pub fn read_delimited(
+    delim: u8,
+    max_record: usize,
+) -> Result<Option<alloc::vec::Vec<u8>>, FrameError> {
+    let mut reader = stdin();
+    let mut record = alloc::vec::Vec::new();
+    let mut too_large = false;
+    let mut read_any = false;
+    let mut byte = 0u8;
+    loop {
+        if reader.read_bytes_all(core::slice::from_mut(&mut byte)) == 0 {
+            break;
+        }
+        read_any = true;
+        if byte == delim {
+            break;
+        }
+        if record.len() < max_record {
+            record.push(byte);
+        } else {
+            too_large = true;
+        }
+    }
+    if too_large {
+        return Err(FrameError::RecordTooLarge { max: max_record });
+    }
+    Ok(read_any.then_some(record))
+}
+
+/// Read a frame with a little-endian length header from the host via `stdin`.
+///
+/// This is the same as [read_frame], with the byte order of the length header
+/// made explicit.
+#[stability::unstable]
+pub fn read_frame_le() -> alloc::vec::Vec<u8> {
+    read_frame()
+}
+
+/// Read a frame with a big-endian length header from the host via `stdin`.
+///
+/// This is useful for frames produced by systems other than the risc0 host,
+/// which often use network byte order for the length.
+#[stability::unstable]
+pub fn read_frame_be() -> alloc::vec::Vec<u8> {
+    let len = u32::from_be_bytes(read_frame_len_bytes()) as usize;
+    let mut bytes = vec![0u8; len];
     read_slice(&mut bytes);
     bytes
 }
 
+/// Read a frame from the host via `stdin` into the given buffer.
+///
+/// The buffer is resized to the length of the frame, reusing its capacity
+/// across calls. This avoids an allocation per frame when reading many frames.
+/// Any bytes from a previous frame are cleared. Returns the length of the frame.
+#[stability::unstable]
+pub fn read_frame_into(buf: &mut alloc::vec::Vec<u8>) -> usize {
+    let len = read_frame_len();
+    buf.clear();
+    buf.resize(len, 0);
+    read_slice(buf.as_mut_slice());
+    len
+}
+
+/// Read a frame from the host via `stdin`, rejecting frames longer than `max` bytes.
+///
+/// The length header is checked against `max` before the payload buffer is allocated.
+/// A payload shorter than its length header is returned as an error.
+#[stability::unstable]
+pub fn read_frame_capped(max: usize) -> Result<alloc::vec::Vec<u8>, FrameError> {
+    read_frame_payload(read_frame_len(), max)
+}
+
+fn read_frame_payload(len: usize, max: usize) -> Result<alloc::vec::Vec<u8>, FrameError> {
+    if len > max {
+        return Err(FrameError::TooLarge { len, max });
+    }
+    let mut bytes = vec![0u8; len];
+    read_slice_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
 /// Read a frame from the host via `stdin` and deserialize it using the `risc0` codec.
 #[stability::unstable]
 pub fn read_framed<T: DeserializeOwned>() -> Result<T, crate::serde::Error> {
     crate::serde::from_slice(&read_frame())
 }
 
+/// Read a frame from the host via `stdin`, rejecting frames longer than `max`
+/// bytes, and deserialize it using the `risc0` codec.
+///
+/// The length header is checked against `max` before the payload buffer is allocated.
+#[stability::unstable]
+pub fn read_framed_capped<T: DeserializeOwned>(max: usize) -> Result<T, FrameError> {
+    Ok(crate::serde::from_slice(&read_frame_capped(max)?)?)
+}
+
+/// Return an iterator over the frames sent by the host via `stdin`, rejecting
+/// frames longer than `max` bytes, and deserializing each one using the `risc0`
+/// codec.
+///
+/// The iterator ends when the host sends a zero-length frame or `stdin` reaches
+/// the end of input. Errors are returned as items rather than causing a panic.
+/// A deserialization error is returned for that frame only, while a frame that
+/// is too long or shorter than its length header leaves the stream in an
+/// unknown position, so the iterator ends after returning the error.
+#[stability::unstable]
+pub fn frames<T: DeserializeOwned>(max: usize) -> impl Iterator<Item = Result<T, FrameError>> {
+    let mut done = false;
+    core::iter::from_fn(move || {
+        let mut len: u32 = 0;
+        if done || stdin().read_words(core::slice::from_mut(&mut len)).is_err() || len == 0 {
+            return None;
+        }
+        let bytes = match read_frame_payload(len as usize, max) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                done = true;
+                return Some(Err(err));
+            }
+        };
+        Some(crate::serde::from_slice(&bytes).map_err(FrameError::from))
+    })
+    .fuse()
+}
+
+fn read_frame_len() -> usize {
+    u32::from_le_bytes(read_frame_len_bytes()) as usize
+}
+
+fn read_frame_len_bytes() -> [u8; 4] {
+    let mut len: u32 = 0;
+    read_slice(core::slice::from_mut(&mut len));
+    len.to_ne_bytes()
+}
+
+/// Error encountered while reading a frame from the host.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FrameError {
+    /// The length header of the frame exceeds the maximum allowed length.
+    TooLarge {
+        /// Length of the frame, as reported by the host.
+        len: usize,
+        /// Maximum allowed length of the frame.
+        max: usize,
+    },
+
+    /// The frame payload could not be deserialized.
+    Deserialize(crate::serde::Error),
+
+    /// A delimited record exceeds the maximum allowed length.
+    RecordTooLarge {
+        /// Maximum allowed length of the record.
+        max: usize,
+    },
+}
+
+impl From<crate::serde::Error> for FrameError {
+    fn from(err: crate::serde::Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+impl core::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            FrameError::TooLarge { len, max } => {
+                write!(f, "frame length {len} exceeds maximum of {max}")
+            }
+            FrameError::Deserialize(err) => write!(f, "failed to deserialize frame: {err}"),
+            FrameError::RecordTooLarge { max } => {
+                write!(f, "record exceeds maximum of {max} bytes")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FrameError {}
+
+/// Maximum buffer capacity allocated by [read_buffered], in bytes.
+#[cfg(feature = "std")]
+const MAX_READ_BUFFERED_CAPACITY: usize = 64 * 1024;
+
 /// Internal API used for testing. Do not use.
+///
+/// Reads a value preceded by a `u32` byte length header. The length is provided
+/// by the host, so the buffer capacity is limited to 64 KiB regardless of the
+/// header, and the value is not read past the given length.
 #[stability::unstable]
 #[cfg(feature = "std")]
 pub fn read_buffered<T: DeserializeOwned>() -> Result<T, crate::serde::Error> {
+    use std::io::Read as _;
+
     let mut len: u32 = 0;
     read_slice(core::slice::from_mut(&mut len));
-    let reader = std::io::BufReader::with_capacity(len as usize, stdin());
+    let capacity = (len as usize).min(MAX_READ_BUFFERED_CAPACITY);
+    let reader = std::io::BufReader::with_capacity(capacity, stdin().take(len as u64));
     T::deserialize(&mut crate::serde::Deserializer::new(reader))
 }
 
@@ -496,14 +1633,64 @@ pub fn keccak_digest(input: &[u8], _delim: u8) -> Result<[u8; 32]> {
             input.len(),
             nondet_digest.as_ptr() as *mut [u32; DIGEST_WORDS],
         );
-        KECCAK_BATCHER
-            .write_keccak_entry(input, &nondet_digest)
-            .unwrap();
+        KECCAK_BATCHER.write_keccak_entry(input, &nondet_digest)?;
     };
 
     Ok(nondet_digest)
 }
 
+/// Compute the SHA-256 digest of `data`.
+///
+/// This uses [sha::Impl][crate::sha::Impl], the same implementation used to
+/// hash the journal. In the zkVM, it runs on the SHA-256 accelerator, which is
+/// much cheaper than hashing with a software SHA-256 crate in the guest.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let data: Vec<u8> = env::read();
+/// env::commit(&env::sha256(&data));
+/// ```
+pub fn sha256(data: &[u8]) -> Digest {
+    *crate::sha::Impl::hash_bytes(data)
+}
+
+/// Compute the keccak-256 hash of `input` using the keccak accelerator.
+///
+/// The hash is computed by the host and returned without being checked by the
+/// guest. Instead, the input and hash are recorded as an entry in
+/// [KECCAK_BATCHER], and the batched transcript is proven separately by the
+/// keccak circuit. This gives the guest both the hash and the obligation to
+/// prove it in one call, without hashing the input in the guest.
+///
+/// Entries are accumulated until the next one would not fit in
+/// [KeccakBatcher::KECCAK_LIMIT] bytes, at which point the transcript is
+/// finalized and a new batch is started. Any remaining entries are finalized
+/// when the guest exits. A single input, padded to a multiple of
+/// [KeccakBatcher::BLOCK_BYTES], must fit in one batch along with its hash,
+/// and span at most [KeccakBatcher::MAX_ENTRY_BLOCKS] blocks; larger inputs
+/// return an error.
+#[cfg(feature = "unstable")]
+pub fn keccak256(input: &[u8]) -> Result<[u8; 32]> {
+    keccak_digest(input, 0x01)
+}
+
+/// Commit a summary of the given [KeccakBatcher] to the journal.
+///
+/// The summary is the digest of the input transcript, as returned by
+/// [KeccakBatcher::transcript_digest], followed by the number of keccak blocks
+/// in the transcript as a little-endian `u32`, for a total of 36 bytes. This
+/// lets a verifier check how many keccak permutations the batch proves. On the
+/// host, use [keccak::decode_summary][crate::keccak::decode_summary] to decode
+/// it.
+#[cfg(feature = "unstable")]
+pub fn commit_keccak_summary(batcher: &KeccakBatcher) {
+    commit_slice(batcher.transcript_digest().as_words());
+    commit_slice(&[batcher.block_count()]);
+}
+
 /// Used for batching keccak proofs
 #[cfg(feature = "unstable")]
 pub static mut KECCAK_BATCHER: KeccakBatcher = KeccakBatcher::init();
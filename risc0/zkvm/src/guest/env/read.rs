@@ -58,7 +58,7 @@ impl FdReader {
 
     // Like read_bytes, but fills the buffer completely or until EOF occurs.
     #[must_use = "read_bytes_all can potentially return EOF; this case should be handled."]
-    fn read_bytes_all(&mut self, mut buf: &mut [u8]) -> usize {
+    pub(crate) fn read_bytes_all(&mut self, mut buf: &mut [u8]) -> usize {
         let mut tot_read = 0;
         while !buf.is_empty() {
             let nread = self.read_bytes(buf);
@@ -119,17 +119,13 @@ impl WordRead for FdReader {
 }
 
 #[cfg(feature = "std")]
-impl WordRead for std::io::BufReader<FdReader> {
+impl<R: std::io::Read> WordRead for std::io::BufReader<R> {
     fn read_words(&mut self, words: &mut [u32]) -> crate::serde::Result<()> {
         use std::io::Read;
-        let nread_bytes = self
-            .read(bytemuck::cast_slice_mut(words))
-            .map_err(|_| crate::serde::Error::DeserializeUnexpectedEnd)?;
-        if nread_bytes == words.len() * WORD_SIZE {
-            Ok(())
-        } else {
-            Err(crate::serde::Error::DeserializeUnexpectedEnd)
-        }
+        // Read until the words are filled, since the buffer may be smaller than
+        // the request or the inner reader may return short reads.
+        self.read_exact(bytemuck::cast_slice_mut(words))
+            .map_err(|_| crate::serde::Error::DeserializeUnexpectedEnd)
     }
 
     fn read_padded_bytes(&mut self, bytes: &mut [u8]) -> crate::serde::Result<()> {
@@ -20,7 +20,7 @@ use risc0_zkvm_platform::syscall::sys_verify_integrity;
 
 use crate::{sha::Digestible, Assumption, MaybePruned, PrunedValueError, ReceiptClaim};
 
-use super::ASSUMPTIONS_DIGEST;
+use super::{commit_slice, ASSUMPTIONS_DIGEST};
 
 /// Verify there exists a receipt for an execution with `image_id` and `journal`.
 ///
@@ -66,6 +66,30 @@ pub fn verify(image_id: impl Into<Digest>, journal: &[impl Pod]) -> Result<(), I
     Ok(())
 }
 
+/// Verify there exists a receipt for an execution with `image_id` and `journal`, and commit the
+/// digest of `journal`.
+///
+/// This adds the same assumption as [verify], and then commits the 32 byte SHA-256 digest of
+/// `journal` to the journal of the current execution, as raw bytes with
+/// [commit_slice][super::commit_slice]. The digest is the same value as the journal digest in the
+/// [ReceiptClaim][crate::ReceiptClaim] of the verified receipt, so the public output of the
+/// composed proof binds the journal of the verified receipt. Calling this several times commits
+/// the digests back-to-back in the order the receipts are verified.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use risc0_zkvm::guest::env;
+///
+/// # let HELLO_WORLD_ID = Digest::ZERO;
+/// env::verify_and_commit(HELLO_WORLD_ID, b"hello world".as_slice()).unwrap();
+/// ```
+pub fn verify_and_commit(image_id: impl Into<Digest>, journal: &[u8]) -> Result<(), Infallible> {
+    verify(image_id, journal)?;
+    commit_slice(journal.digest().as_bytes());
+    Ok(())
+}
+
 /// Verify that there exists a valid receipt with the specified [ReceiptClaim][crate::ReceiptClaim].
 ///
 /// Calling this function in the guest is logically equivalent to verifying a receipt with the same
@@ -179,3 +203,26 @@ pub fn verify_assumption(claim: Digest, control_root: Digest) -> Result<(), Infa
 
     Ok(())
 }
+
+/// Verify the first of several candidate claims that matches the given predicate.
+///
+/// Each candidate is a `(claim, control_root)` pair, as passed to [verify_assumption]. The
+/// predicate is evaluated on the candidates in order, and only the first candidate for which it
+/// returns `true` is verified and added to the assumptions. Its index is returned. If no
+/// candidate matches, nothing is verified and `None` is returned.
+///
+/// Candidates that do not match are never sent to the host, so they do not affect the
+/// assumptions digest. Note that, as with [verify_assumption], the host must be able to provide a
+/// receipt for the selected candidate; otherwise execution does not continue. The predicate is
+/// therefore the mechanism for choosing a candidate, not a fallback on verification failure.
+pub fn verify_first_matching(
+    candidates: &[(Digest, Digest)],
+    mut predicate: impl FnMut(&Digest, &Digest) -> bool,
+) -> Option<usize> {
+    let index = candidates
+        .iter()
+        .position(|(claim, control_root)| predicate(claim, control_root))?;
+    let (claim, control_root) = candidates[index];
+    verify_assumption(claim, control_root).unwrap();
+    Some(index)
+}
@@ -18,6 +18,12 @@ use serde::Serialize;
 
 use crate::serde::{Serializer, WordWrite};
 
+/// Maximum number of bytes passed to the host in a single write syscall.
+///
+/// Larger writes are split into chunks of this size. The hook of a [FdWriter]
+/// receives the chunks in order, so e.g. the journal digest is unchanged.
+pub const MAX_WRITE_CHUNK_BYTES: usize = 64 * 1024;
+
 /// Serializes and writes objects.
 pub trait Write {
     /// Write a serialized object.
@@ -50,8 +56,10 @@ impl<F: Fn(&[u8])> FdWriter<F> {
     }
 
     fn write_bytes(&mut self, bytes: &[u8]) {
-        unsafe { sys_write(self.fd, bytes.as_ptr(), bytes.len()) }
-        (self.hook)(bytes);
+        for chunk in bytes.chunks(MAX_WRITE_CHUNK_BYTES) {
+            unsafe { sys_write(self.fd, chunk.as_ptr(), chunk.len()) }
+            (self.hook)(chunk);
+        }
     }
 }
 
@@ -82,6 +90,55 @@ impl<F: Fn(&[u8])> WordWrite for FdWriter<F> {
     }
 }
 
+/// A writer that forwards everything written to it to two [FdWriter]s.
+///
+/// Each write goes to the first writer and then the second, so the bytes seen
+/// by either writer, and e.g. the journal digest, are the same as writing to it
+/// directly. Values are serialized once for both writers.
+pub struct TeeWriter<F: Fn(&[u8]), G: Fn(&[u8])> {
+    first: FdWriter<F>,
+    second: FdWriter<G>,
+}
+
+impl<F: Fn(&[u8]), G: Fn(&[u8])> TeeWriter<F, G> {
+    /// Creates a new TeeWriter writing to both `first` and `second`.
+    pub fn new(first: FdWriter<F>, second: FdWriter<G>) -> Self {
+        TeeWriter { first, second }
+    }
+
+    /// Splits the TeeWriter into its two writers.
+    pub fn into_inner(self) -> (FdWriter<F>, FdWriter<G>) {
+        (self.first, self.second)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.first.write_bytes(bytes);
+        self.second.write_bytes(bytes);
+    }
+}
+
+impl<F: Fn(&[u8]), G: Fn(&[u8])> Write for TeeWriter<F, G> {
+    fn write<T: Serialize>(&mut self, val: T) {
+        val.serialize(&mut Serializer::new(self)).unwrap();
+    }
+
+    fn write_slice<T: Pod>(&mut self, buf: &[T]) {
+        self.write_bytes(bytemuck::cast_slice(buf));
+    }
+}
+
+impl<F: Fn(&[u8]), G: Fn(&[u8])> WordWrite for TeeWriter<F, G> {
+    fn write_words(&mut self, words: &[u32]) -> crate::serde::Result<()> {
+        self.first.write_words(words)?;
+        self.second.write_words(words)
+    }
+
+    fn write_padded_bytes(&mut self, bytes: &[u8]) -> crate::serde::Result<()> {
+        self.first.write_padded_bytes(bytes)?;
+        self.second.write_padded_bytes(bytes)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<F: Fn(&[u8])> std::io::Write for FdWriter<F> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
@@ -93,3 +150,15 @@ impl<F: Fn(&[u8])> std::io::Write for FdWriter<F> {
         Ok(())
     }
 }
+
+#[cfg(feature = "std")]
+impl<F: Fn(&[u8]), G: Fn(&[u8])> std::io::Write for TeeWriter<F, G> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -208,5 +208,8 @@ pub fn memory_barrier<T>(ptr: *const T) {
 #[cfg(all(target_os = "zkvm", not(feature = "std")))]
 #[panic_handler]
 fn panic_impl(panic_info: &core::panic::PanicInfo) -> ! {
+    if env::take_panic_committer() {
+        env::commit_panic(panic_info, panic_info.location());
+    }
     risc0_zkvm_platform::rust_rt::panic_fault(panic_info);
 }
@@ -35,7 +35,7 @@ use tempfile::TempDir;
 use crate::{
     host::client::{
         posix_io::PosixIo,
-        slice_io::{slice_io_from_fn, SliceIo, SliceIoTable},
+        slice_io::{slice_io_from_fn, SliceIo, SliceIoBuilder, SliceIoTable},
     },
     serde::to_vec,
     AssumptionReceipt, TraceCallback,
@@ -86,6 +86,76 @@ pub struct ProveKeccakRequest {
     pub input: Vec<u8>,
 }
 
+#[cfg(feature = "unstable")]
+impl ProveKeccakRequest {
+    /// Maximum size of the input transcript, in bytes.
+    ///
+    /// This is the capacity of the transcript of the guest
+    /// [KeccakBatcher][crate::guest::env::KeccakBatcher].
+    pub const MAX_INPUT_BYTES: usize = crate::guest::env::KeccakBatcher::KECCAK_LIMIT;
+
+    /// Construct a [ProveKeccakRequestBuilder] that validates the request.
+    pub fn builder() -> ProveKeccakRequestBuilder {
+        ProveKeccakRequestBuilder::default()
+    }
+}
+
+/// A builder pattern used to construct a [ProveKeccakRequest].
+#[cfg(feature = "unstable")]
+#[stability::unstable]
+#[derive(Default)]
+pub struct ProveKeccakRequestBuilder {
+    po2: Option<usize>,
+    input: Vec<u8>,
+}
+
+#[cfg(feature = "unstable")]
+impl ProveKeccakRequestBuilder {
+    /// Set the po2 of the keccak circuit to run.
+    ///
+    /// The po2 is not checked against the sizes supported by the keccak circuit, since the
+    /// circuit parameters are not available in this crate. An unsupported po2 is reported by the
+    /// keccak prover.
+    pub fn po2(&mut self, po2: usize) -> &mut Self {
+        self.po2 = Some(po2);
+        self
+    }
+
+    /// Set the input transcript to provide to the keccak circuit.
+    ///
+    /// Must be a whole number of words and at most
+    /// [ProveKeccakRequest::MAX_INPUT_BYTES] long.
+    pub fn input(&mut self, input: Vec<u8>) -> &mut Self {
+        self.input = input;
+        self
+    }
+
+    /// Validate this builder and construct a [ProveKeccakRequest].
+    ///
+    /// Returns an error if the po2 is not set, or if the input does not fit in
+    /// a keccak transcript. The value of the po2 is not validated.
+    pub fn build(&mut self) -> Result<ProveKeccakRequest> {
+        let Some(po2) = self.po2 else {
+            anyhow::bail!("keccak po2 is not set");
+        };
+        anyhow::ensure!(
+            self.input.len() <= ProveKeccakRequest::MAX_INPUT_BYTES,
+            "keccak input of {} bytes exceeds the limit of {} bytes",
+            self.input.len(),
+            ProveKeccakRequest::MAX_INPUT_BYTES
+        );
+        anyhow::ensure!(
+            self.input.len() % risc0_zkvm_platform::WORD_SIZE == 0,
+            "keccak input of {} bytes is not a whole number of words",
+            self.input.len()
+        );
+        Ok(ProveKeccakRequest {
+            po2,
+            input: mem::take(&mut self.input),
+        })
+    }
+}
+
 /// A Keccak proof response
 #[stability::unstable]
 pub struct ProveKeccakResponse {
@@ -130,6 +200,7 @@ pub struct ExecutorEnv<'a> {
     pub(crate) posix_io: Rc<RefCell<PosixIo<'a>>>,
     pub(crate) slice_io: Rc<RefCell<SliceIoTable<'a>>>,
     pub(crate) input: Vec<u8>,
+    pub(crate) stdin_input: Option<Rc<RefCell<Cursor<Vec<u8>>>>>,
     pub(crate) trace: Vec<Rc<RefCell<dyn TraceCallback + 'a>>>,
     pub(crate) assumptions: Rc<RefCell<AssumptionReceipts>>,
     pub(crate) segment_path: Option<SegmentPath>,
@@ -137,6 +208,7 @@ pub struct ExecutorEnv<'a> {
     pub(crate) input_digest: Option<Digest>,
     pub(crate) coprocessor: Option<CoprocessorCallbackRef<'a>>,
     pub(crate) keccak_coprocessor: Option<KeccakCoprocessorCallbackRef<'a>>,
+    pub(crate) disable_fork: bool,
 }
 
 impl<'a> ExecutorEnv<'a> {
@@ -171,11 +243,12 @@ impl<'a> ExecutorEnvBuilder<'a> {
         let mut inner = mem::take(&mut self.inner);
 
         if !inner.input.is_empty() {
-            let reader = Cursor::new(inner.input.clone());
+            let reader = Rc::new(RefCell::new(Cursor::new(inner.input.clone())));
             inner
                 .posix_io
                 .borrow_mut()
-                .with_read_fd(fileno::STDIN, reader);
+                .with_shared_read_fd(fileno::STDIN, reader.clone());
+            inner.stdin_input = Some(reader);
         }
 
         if inner.pprof_out.is_none() {
@@ -220,6 +293,15 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Disable `sys_fork` for the guest.
+    ///
+    /// When disabled, `sys_fork` fails with `-1` and no child process is created. Guests using
+    /// `env::run_unconstrained` then run the closure inline, as part of the proven execution.
+    pub fn disable_fork(&mut self) -> &mut Self {
+        self.inner.disable_fork = true;
+        self
+    }
+
     /// Add environment variables to the guest environment.
     ///
     /// # Example
@@ -339,6 +421,8 @@ impl<'a> ExecutorEnvBuilder<'a> {
     /// can be more efficient than deserializing a message on-demand. On-demand
     /// deserialization can cause many syscalls, whereas a frame will only have
     /// two.
+    ///
+    /// The length header is written as a little-endian `u32`.
     #[stability::unstable]
     pub fn write_frame(&mut self, payload: &[u8]) -> &mut Self {
         let len = payload.len() as u32;
@@ -347,6 +431,26 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Write a frame with a little-endian length header to the zkVM guest via stdin.
+    ///
+    /// This is the same as [ExecutorEnvBuilder::write_frame], with the byte order of the length
+    /// header made explicit.
+    #[stability::unstable]
+    pub fn write_frame_le(&mut self, payload: &[u8]) -> &mut Self {
+        self.write_frame(payload)
+    }
+
+    /// Write a frame with a big-endian length header to the zkVM guest via stdin.
+    ///
+    /// The guest reads it with `env::read_frame_be`.
+    #[stability::unstable]
+    pub fn write_frame_be(&mut self, payload: &[u8]) -> &mut Self {
+        let len = payload.len() as u32;
+        self.inner.input.extend_from_slice(&len.to_be_bytes());
+        self.inner.input.extend_from_slice(payload);
+        self
+    }
+
     /// Add a posix-style standard input.
     pub fn stdin(&mut self, reader: impl Read + 'a) -> &mut Self {
         self.read_fd(fileno::STDIN, BufReader::new(reader))
@@ -396,6 +500,18 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Add all the handlers registered with the given [SliceIoBuilder].
+    ///
+    /// Handlers replace any previously added handler for the same syscall.
+    pub fn slice_io_handlers(&mut self, handlers: SliceIoBuilder<'a>) -> &mut Self {
+        self.inner
+            .slice_io
+            .borrow_mut()
+            .inner
+            .extend(handlers.table.inner);
+        self
+    }
+
     /// Add an [AssumptionReceipt] to the [ExecutorEnv], for use in [composition].
     ///
     /// During execution, when the guest calls `env::verify` or `env::verify_integrity`, this
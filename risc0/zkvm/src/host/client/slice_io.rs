@@ -16,6 +16,7 @@ use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
 use anyhow::Result;
 use bytes::Bytes;
+use risc0_zkvm_platform::syscall::SyscallName;
 
 /// An I/O handler that returns arbitrary data to the guest.
 ///
@@ -67,6 +68,51 @@ impl<'a> SliceIoTable<'a> {
     }
 }
 
+/// A set of I/O handlers, keyed by syscall name, to add to an
+/// [ExecutorEnv][crate::ExecutorEnv].
+///
+/// Each handler receives the bytes sent by the guest with `env::send_recv_slice`
+/// and returns the bytes to send back to the guest.
+///
+/// # Example
+///
+/// ```
+/// use risc0_zkvm::{declare_syscall, ExecutorEnv, SliceIoBuilder};
+///
+/// declare_syscall!(SYS_REVERSE);
+///
+/// let mut handlers = SliceIoBuilder::new();
+/// handlers.handler(SYS_REVERSE, |from_guest| from_guest.iter().rev().copied().collect());
+/// let env = ExecutorEnv::builder()
+///     .slice_io_handlers(handlers)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct SliceIoBuilder<'a> {
+    pub(crate) table: SliceIoTable<'a>,
+}
+
+impl<'a> SliceIoBuilder<'a> {
+    /// Construct an empty [SliceIoBuilder].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for the given syscall, replacing any previous handler.
+    pub fn handler(
+        &mut self,
+        syscall: SyscallName,
+        handler: impl Fn(&[u8]) -> Vec<u8> + 'a,
+    ) -> &mut Self {
+        self.table.with_handler(
+            syscall.as_str(),
+            slice_io_from_fn(move |from_guest: Bytes| Ok(handler(&from_guest).into())),
+        );
+        self
+    }
+}
+
 impl<'a> SliceIo for Rc<RefCell<dyn SliceIo + 'a>> {
     fn handle_io(&mut self, syscall: &str, from_guest: Bytes) -> Result<Bytes> {
         self.borrow_mut().handle_io(syscall, from_guest)
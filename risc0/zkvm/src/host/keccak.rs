@@ -0,0 +1,265 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host-side helpers for the keccak accelerator.
+//!
+//! The guest batches calls to the keccak accelerator using a
+//! [KeccakBatcher][crate::guest::env::KeccakBatcher]. The functions in this module mirror the
+//! batcher's transcript format so that the host can compute the values a guest is expected to
+//! produce.
+
+use std::collections::VecDeque;
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "prove")]
+use anyhow::{ensure, Context};
+use risc0_binfmt::read_sha_halfs;
+use risc0_zkp::core::{
+    digest::{Digest, DIGEST_BYTES, DIGEST_SHORTS},
+    hash::sha::Sha256,
+};
+use sha3::{Digest as _, Keccak256};
+
+use crate::guest::env::KeccakBatcher;
+#[cfg(feature = "prove")]
+use crate::{receipt_claim::Unknown, sha::Digestible, ReceiptClaim, SuccinctReceipt};
+
+/// Compute the digest that a [KeccakBatcher] produces for the given messages.
+///
+/// The transcript is laid out as a sequence of entries, each consisting of a
+/// [KeccakBatcher::BLOCK_COUNT_BYTES] header holding the number of blocks in the entry, the
+/// message padded up to a multiple of [KeccakBatcher::BLOCK_BYTES], and the keccak-256 digest of
+/// the message. The transcript is terminated by a zeroed block count header and hashed with
+/// SHA-256.
+///
+/// All messages are expected to fit in a single batch of [KeccakBatcher::KECCAK_LIMIT] bytes.
+pub fn expected_transcript_digest(messages: &[&[u8]]) -> Digest {
+    let mut transcript = Vec::new();
+    for message in messages {
+        let header = transcript.len();
+        transcript.resize(header + KeccakBatcher::BLOCK_COUNT_BYTES, 0);
+
+        let data = transcript.len();
+        transcript.extend_from_slice(message);
+        transcript.extend_from_slice(&KeccakBatcher::padding(
+            message.len(),
+            KeccakBatcher::BLOCK_BYTES,
+        ));
+
+        transcript[header] = ((transcript.len() - data) / KeccakBatcher::BLOCK_BYTES) as u8;
+        transcript.extend_from_slice(&Keccak256::digest(message));
+    }
+    transcript.resize(transcript.len() + KeccakBatcher::BLOCK_COUNT_BYTES, 0);
+
+    *crate::sha::Impl::hash_bytes(&transcript)
+}
+
+/// Return the largest message, in bytes, that fits in a single [KeccakBatcher] entry.
+///
+/// An entry spans at most [KeccakBatcher::MAX_ENTRY_BLOCKS] blocks, and a batch holds
+/// [KeccakBatcher::KECCAK_LIMIT] bytes, including the block count header, padded message, and
+/// digest of the entry, and the zeroed block count that terminates the transcript. The message
+/// leaves room in its last block for the shortest padding, see [KeccakBatcher::padding_len].
+pub fn max_batch_message_len() -> usize {
+    let blocks = (KeccakBatcher::KECCAK_LIMIT
+        - KeccakBatcher::BLOCK_COUNT_BYTES
+        - DIGEST_BYTES
+        - KeccakBatcher::FINAL_PADDING_BYTES)
+        / KeccakBatcher::BLOCK_BYTES;
+    let len = blocks.min(KeccakBatcher::MAX_ENTRY_BLOCKS) * KeccakBatcher::BLOCK_BYTES;
+    len - KeccakBatcher::padding_len(len - 1, KeccakBatcher::BLOCK_BYTES)
+}
+
+/// Split an input of `input_len` bytes into messages that each fit in a single [KeccakBatcher]
+/// entry.
+///
+/// Returns the `(offset, len)` span of each message in the input. Every message except the last
+/// is [max_batch_message_len] bytes long, and a guest hashing the messages in order with a
+/// [KeccakBatcher] writes one entry per span. An empty input is a single empty message.
+pub fn plan_batches(input_len: usize) -> Vec<(usize, usize)> {
+    if input_len == 0 {
+        return vec![(0, 0)];
+    }
+    let max_len = max_batch_message_len();
+    (0..input_len)
+        .step_by(max_len)
+        .map(|offset| (offset, max_len.min(input_len - offset)))
+        .collect()
+}
+
+/// Extract the claim digest from the seal of a keccak circuit proof.
+///
+/// The keccak circuit writes the digest of its claim to the start of the seal as
+/// [DIGEST_SHORTS] 16-bit halves, each stored in a `u32`. This can be used to inspect a raw
+/// keccak seal without proving it again.
+pub fn claim_digest_from_seal(seal: &[u32]) -> Result<Digest> {
+    let mut halfs: VecDeque<u32> = seal.iter().take(DIGEST_SHORTS).copied().collect();
+    read_sha_halfs(&mut halfs).map_err(|err| anyhow!("invalid keccak seal: {err}"))
+}
+
+/// Decode a summary committed by `env::commit_keccak_summary` in the guest.
+///
+/// The summary is the 32 byte digest of the batched keccak transcript followed by the number of
+/// keccak blocks in the transcript as a little-endian `u32`. `bytes` must start with the summary,
+/// e.g. a slice of the journal at the offset it was committed. Returns the digest and the block
+/// count.
+pub fn decode_summary(bytes: &[u8]) -> Result<(Digest, u32)> {
+    let summary = bytes
+        .get(..DIGEST_BYTES + 4)
+        .ok_or_else(|| anyhow!("keccak summary must be {} bytes", DIGEST_BYTES + 4))?;
+    let (digest, block_count) = summary.split_at(DIGEST_BYTES);
+    Ok((
+        Digest::try_from(digest)?,
+        u32::from_le_bytes(block_count.try_into()?),
+    ))
+}
+
+/// Resolve the keccak assumption of `main` using a receipt for the keccak claim.
+///
+/// A guest that uses the keccak accelerator adds an [Assumption][crate::Assumption] for the claim
+/// of the keccak proof. This runs the recursion resolve program to discharge that assumption,
+/// producing a receipt for `main` without it.
+///
+/// The assumption must be at the head of the assumptions list of `main`, and its claim digest must
+/// equal the digest of the claim of `keccak`. This is checked before running the resolve program,
+/// which additionally checks that `keccak` was produced by a program in the control root of the
+/// assumption.
+#[cfg(feature = "prove")]
+pub fn resolve_into(
+    main: &SuccinctReceipt<ReceiptClaim>,
+    keccak: &SuccinctReceipt<Unknown>,
+) -> Result<SuccinctReceipt<ReceiptClaim>> {
+    let head = main
+        .claim
+        .as_value()
+        .context("main receipt claim is pruned")?
+        .output
+        .as_value()
+        .context("main receipt output is pruned")?
+        .as_ref()
+        .context("main receipt has empty output and no assumptions")?
+        .assumptions
+        .as_value()
+        .context("main receipt assumptions are pruned")?
+        .0
+        .first()
+        .context("main receipt has no assumptions")?
+        .as_value()
+        .context("main receipt head assumption is pruned")?;
+
+    let keccak_claim = keccak.claim.digest();
+    ensure!(
+        head.claim == keccak_claim,
+        "keccak claim {keccak_claim} does not match the head assumption claim {}",
+        head.claim
+    );
+
+    crate::recursion::resolve(main, keccak)
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_binfmt::write_sha_halfs;
+    use sha3::{Digest as _, Keccak256};
+
+    use super::{
+        claim_digest_from_seal, expected_transcript_digest, max_batch_message_len, plan_batches,
+    };
+    use crate::{guest::env::KeccakBatcher, sha::Digest};
+
+    #[test]
+    fn transcript_matches_batcher() {
+        let messages: [&[u8]; 4] = [b"", b"abc", &[7u8; 135], &[9u8; 300]];
+
+        let mut batcher = KeccakBatcher::default();
+        for message in messages {
+            let hash: [u8; 32] = Keccak256::digest(message).into();
+            batcher.write_keccak_entry(message, &hash).unwrap();
+        }
+
+        assert_eq!(
+            expected_transcript_digest(&messages),
+            batcher.finalize_transcript()
+        );
+    }
+
+    #[test]
+    fn plan_batches_three() {
+        let max_len = max_batch_message_len();
+        assert_eq!(
+            plan_batches(2 * max_len + 1),
+            [(0, max_len), (max_len, max_len), (2 * max_len, 1)]
+        );
+        assert_eq!(plan_batches(max_len), [(0, max_len)]);
+        assert_eq!(plan_batches(0), [(0, 0)]);
+
+        // Each planned message fits in a single entry of the largest size.
+        let mut batcher = KeccakBatcher::default();
+        batcher
+            .write_keccak_entry(&vec![1u8; max_len], &[0u8; 32])
+            .unwrap();
+        assert_eq!(
+            batcher.block_count() as usize,
+            KeccakBatcher::MAX_ENTRY_BLOCKS
+        );
+        assert_eq!(
+            max_len % KeccakBatcher::BLOCK_BYTES,
+            KeccakBatcher::BLOCK_BYTES - 1
+        );
+        assert!(batcher
+            .write_keccak_entry(&vec![1u8; max_len + 1], &[0u8; 32])
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "client")]
+    fn prove_keccak_request_builder() {
+        use crate::ProveKeccakRequest;
+
+        let request = ProveKeccakRequest::builder()
+            .po2(15)
+            .input(vec![0u8; ProveKeccakRequest::MAX_INPUT_BYTES])
+            .build()
+            .unwrap();
+        assert_eq!(request.po2, 15);
+        assert_eq!(request.input.len(), ProveKeccakRequest::MAX_INPUT_BYTES);
+
+        assert!(ProveKeccakRequest::builder().build().is_err());
+
+        let input_err = ProveKeccakRequest::builder()
+            .po2(15)
+            .input(vec![0u8; ProveKeccakRequest::MAX_INPUT_BYTES + 4])
+            .build()
+            .unwrap_err();
+        assert!(input_err.to_string().contains("exceeds the limit"));
+        assert!(ProveKeccakRequest::builder()
+            .po2(15)
+            .input(vec![0u8; 3])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn claim_digest_from_seal_roundtrip() {
+        let claim = Digest::from([0x01020304u32, 5, 6, 7, 8, 9, 10, 0xffffffff]);
+        let mut seal = Vec::new();
+        write_sha_halfs(&mut seal, &claim);
+        seal.extend_from_slice(&[42; 8]);
+        assert_eq!(claim_digest_from_seal(&seal).unwrap(), claim);
+
+        assert!(claim_digest_from_seal(&seal[..15]).is_err());
+        seal[0] = 0x10000;
+        assert!(claim_digest_from_seal(&seal).is_err());
+    }
+}
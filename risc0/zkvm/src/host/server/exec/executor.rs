@@ -276,6 +276,7 @@ impl<'a> NewSyscall for ExecutorImpl<'a> {
             ctx,
             syscall_table: self.syscall_table.clone(),
         };
+        self.syscall_table.count_syscall();
         self.syscall_table
             .get_syscall(syscall)
             .context(format!("Unknown syscall: {syscall:?}"))?
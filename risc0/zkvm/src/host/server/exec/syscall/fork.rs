@@ -24,7 +24,7 @@ use risc0_zkvm_platform::{
     memory::is_guest_memory,
     syscall::{
         ecall,
-        nr::{SYS_EXIT, SYS_FORK},
+        nr::SYS_EXIT,
         reg_abi::{REG_A0, REG_A1, REG_A2, REG_MAX, REG_T0},
     },
     PAGE_SIZE, WORD_SIZE,
@@ -38,8 +38,14 @@ const NUM_PAGES: usize = 192 * 1024;
 
 const PID_CHILD: u32 = 0;
 const PID_PARENT: u32 = 1;
+/// Returned to the guest as `-1` when forking is disabled; no child process is created.
+const PID_ERROR: u32 = u32::MAX;
 
-pub(crate) struct SysFork;
+/// Maximum number of nested child processes, to avoid the possibility of fork bombs.
+const MAX_FORK_DEPTH: usize = 8;
+
+/// Handler for `sys_fork`. Forking is enabled unless the inner value is `false`.
+pub(crate) struct SysFork(pub bool);
 
 impl Syscall for SysFork {
     fn syscall(
@@ -48,6 +54,14 @@ impl Syscall for SysFork {
         ctx: &mut dyn SyscallContext,
         _to_guest: &mut [u32],
     ) -> Result<(u32, u32)> {
+        if !self.0 {
+            return Ok((PID_ERROR, 0));
+        }
+        if ctx.syscall_table().fork_depth >= MAX_FORK_DEPTH {
+            bail!("fork depth limit of {MAX_FORK_DEPTH} exceeded");
+        }
+        // A child process may fork again. Each nested child runs to completion, discarding its
+        // changes to memory, before its parent continues.
         let mut exec = ChildExecutor::new(ctx)?;
         exec.run()?;
         // Return a non-zero value for sys_fork, which means this is the 'parent' process.
@@ -82,9 +96,7 @@ impl<'a, 'b> ChildExecutor<'a, 'b> {
         let mut posix_io = ctx.syscall_table().posix_io.borrow().clone();
         posix_io.write_fds.remove(&fileno::JOURNAL);
         syscall_table.posix_io = Rc::new(RefCell::new(posix_io));
-
-        // avoid the possibility of fork bombs.
-        syscall_table.inner.remove(&SYS_FORK.as_str().to_string());
+        syscall_table.fork_depth += 1;
 
         Ok(Self {
             ctx,
@@ -126,6 +138,7 @@ impl<'a, 'b> ChildExecutor<'a, 'b> {
             return Ok(true);
         }
 
+        self.syscall_table.count_syscall();
         let syscall = self
             .syscall_table
             .get_syscall(&syscall_name)
@@ -27,9 +27,15 @@ mod prove_keccak;
 mod prove_zkr;
 mod random;
 mod slice_io;
+mod stdin_len;
+mod syscall_count;
 mod verify;
 
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 
 use anyhow::{anyhow, Result};
 use risc0_circuit_rv32im::prove::emu::addr::ByteAddr;
@@ -37,8 +43,8 @@ use risc0_zkp::core::digest::Digest;
 use risc0_zkvm_platform::syscall::{
     nr::{
         SYS_ARGC, SYS_ARGV, SYS_CYCLE_COUNT, SYS_FORK, SYS_GETENV, SYS_KECCAK, SYS_LOG, SYS_PANIC,
-        SYS_PIPE, SYS_PROVE_KECCAK, SYS_PROVE_ZKR, SYS_RANDOM, SYS_READ, SYS_VERIFY_INTEGRITY,
-        SYS_WRITE,
+        SYS_PIPE, SYS_PROVE_KECCAK, SYS_PROVE_ZKR, SYS_RANDOM, SYS_READ, SYS_STDIN_LEN,
+        SYS_SYSCALL_COUNT, SYS_VERIFY_INTEGRITY, SYS_WRITE,
     },
     SyscallName, DIGEST_BYTES,
 };
@@ -58,7 +64,7 @@ use self::{
     args::SysArgs, cycle_count::SysCycleCount, fork::SysFork, getenv::SysGetenv, keccak::SysKeccak,
     log::SysLog, panic::SysPanic, pipe::SysPipe, posix_io::SysRead, posix_io::SysWrite,
     prove_keccak::SysProveKeccak, prove_zkr::SysProveZkr, random::SysRandom, slice_io::SysSliceIo,
-    verify::SysVerify,
+    stdin_len::SysStdinLen, syscall_count::SysSyscallCount, verify::SysVerify,
 };
 
 /// A host-side implementation of a system call.
@@ -124,6 +130,8 @@ pub(crate) struct SyscallTable<'a> {
     pub(crate) coprocessor: Option<CoprocessorCallbackRef<'a>>,
     pub(crate) keccak_coprocessor: Option<KeccakCoprocessorCallbackRef<'a>>,
     pub(crate) pending_zkrs: Rc<RefCell<Vec<ProveZkrRequest>>>,
+    pub(crate) syscall_count: Rc<Cell<u64>>,
+    pub(crate) fork_depth: usize,
 }
 
 impl<'a> SyscallTable<'a> {
@@ -136,6 +144,8 @@ impl<'a> SyscallTable<'a> {
             coprocessor: env.coprocessor.clone(),
             keccak_coprocessor: env.keccak_coprocessor.clone(),
             pending_zkrs: Default::default(),
+            syscall_count: Default::default(),
+            fork_depth: 0,
         }
     }
 
@@ -145,7 +155,7 @@ impl<'a> SyscallTable<'a> {
         this.with_syscall(SYS_ARGC, SysArgs(env.args.clone()))
             .with_syscall(SYS_ARGV, SysArgs(env.args.clone()))
             .with_syscall(SYS_CYCLE_COUNT, SysCycleCount)
-            .with_syscall(SYS_FORK, SysFork)
+            .with_syscall(SYS_FORK, SysFork(!env.disable_fork))
             .with_syscall(SYS_GETENV, SysGetenv(env.env_vars.clone()))
             .with_syscall(SYS_KECCAK, SysKeccak)
             .with_syscall(SYS_LOG, SysLog)
@@ -155,6 +165,8 @@ impl<'a> SyscallTable<'a> {
             .with_syscall(SYS_PROVE_KECCAK, SysProveKeccak)
             .with_syscall(SYS_RANDOM, SysRandom)
             .with_syscall(SYS_READ, SysRead)
+            .with_syscall(SYS_STDIN_LEN, SysStdinLen(env.stdin_input.clone()))
+            .with_syscall(SYS_SYSCALL_COUNT, SysSyscallCount)
             .with_syscall(SYS_VERIFY_INTEGRITY, SysVerify)
             .with_syscall(SYS_WRITE, SysWrite);
         for (syscall, handler) in env.slice_io.borrow().inner.iter() {
@@ -179,6 +191,11 @@ impl<'a> SyscallTable<'a> {
     pub(crate) fn get_syscall(&self, name: &str) -> Option<&Rc<RefCell<(dyn Syscall + 'a)>>> {
         self.inner.get(name)
     }
+
+    /// Count a syscall issued by the guest, as reported by `SYS_SYSCALL_COUNT`.
+    pub(crate) fn count_syscall(&self) {
+        self.syscall_count.set(self.syscall_count.get() + 1);
+    }
 }
 
 impl AssumptionReceipts {
@@ -0,0 +1,39 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{cell::RefCell, io::Cursor, rc::Rc};
+
+use anyhow::Result;
+
+use super::{Syscall, SyscallContext};
+
+/// Reports the number of bytes remaining on stdin, if stdin was written by the
+/// [ExecutorEnvBuilder][crate::ExecutorEnvBuilder] and so has a known length.
+pub(crate) struct SysStdinLen(pub Option<Rc<RefCell<Cursor<Vec<u8>>>>>);
+
+impl Syscall for SysStdinLen {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        _ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let Some(input) = &self.0 else {
+            return Ok((0, 0));
+        };
+        let input = input.borrow();
+        let remaining = (input.get_ref().len() as u64).saturating_sub(input.position());
+        Ok((1, remaining.try_into()?))
+    }
+}
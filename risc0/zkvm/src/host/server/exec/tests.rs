@@ -40,8 +40,8 @@ use crate::{
         testutils,
     },
     serde::to_vec,
-    sha::{Digest, Digestible},
-    ExecutorEnv, ExecutorImpl, ExitCode,
+    sha::{Digest, Digestible, DIGEST_BYTES},
+    ExecutorEnv, ExecutorEnvBuilder, ExecutorImpl, ExitCode, Journal, SliceIoBuilder,
 };
 
 fn run_test(spec: MultiTestSpec) {
@@ -193,6 +193,31 @@ fn host_syscall() {
     assert_eq!(*actual.lock().unwrap(), expected[..expected.len() - 1]);
 }
 
+#[test]
+fn host_syscall_slice_io_builder() {
+    let actual: Mutex<Vec<Vec<u8>>> = Vec::new().into();
+    let mut handlers = SliceIoBuilder::new();
+    handlers.handler(SYS_MULTI_TEST, |from_guest| {
+        actual.lock().unwrap().push(from_guest.to_vec());
+        [from_guest, b"x".as_slice()].concat()
+    });
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Syscall { count: 3 })
+        .unwrap()
+        .slice_io_handlers(handlers)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(
+        *actual.lock().unwrap(),
+        vec![b"".to_vec(), b"x".to_vec(), b"xx".to_vec()]
+    );
+}
+
 #[test]
 fn host_syscall_words() {
     let _expected: Vec<u32> = vec![0x01020304];
@@ -211,6 +236,21 @@ fn host_syscall_words() {
     assert_eq!(session.exit_code, ExitCode::Halted(0));
 }
 
+#[test]
+fn host_syscall_words_slop() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::SyscallWordsSlop)
+        .unwrap()
+        .io_callback(SYS_MULTI_TEST_WORDS, Ok)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+}
+
 // Make sure panics in the callback get propagated correctly.
 #[test]
 #[should_panic(expected = "I am panicking from here!")]
@@ -235,152 +275,1084 @@ fn sha_accel() {
     run_test(MultiTestSpec::ShaConforms);
 }
 
-#[test]
-fn sha_cycle_count() {
-    run_test(MultiTestSpec::ShaCycleCount);
-}
+#[test]
+fn sha_cycle_count() {
+    run_test(MultiTestSpec::ShaCycleCount);
+}
+
+#[test]
+fn rsa_compat() {
+    run_test(MultiTestSpec::RsaCompat);
+}
+
+#[test]
+fn bigint_accel() {
+    let cases = testutils::generate_bigint_test_cases(&mut rand::thread_rng(), 10);
+    for case in cases {
+        println!("Running BigInt circuit test case: {:x?}", case);
+        let input = MultiTestSpec::BigInt {
+            x: case.x,
+            y: case.y,
+            modulus: case.modulus,
+        };
+
+        let env = ExecutorEnv::builder()
+            .write(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+        let session = exec.run().unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        assert_eq!(
+            session.journal.unwrap().bytes.as_slice(),
+            bytemuck::cast_slice::<u32, u8>(case.expected().as_slice())
+        );
+    }
+}
+
+#[test]
+fn env_stdio() {
+    const MSG: &str = "Hello world!  This is a test of standard input and output.";
+    const FD: u32 = 123;
+    let spec = to_vec(&MultiTestSpec::EchoStdout { nbytes: 9, fd: FD }).unwrap();
+    let mut stdout: Vec<u8> = Vec::new();
+    {
+        let env = ExecutorEnv::builder()
+            .read_fd(FD, MSG.as_bytes())
+            .stdin(bytemuck::cast_slice(&spec))
+            .stdout(&mut stdout)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+    }
+    assert_eq!(MSG, from_utf8(&stdout).unwrap());
+}
+
+#[test]
+fn tee() {
+    let bytes = b"hello".to_vec();
+    let mut stdout: Vec<u8> = Vec::new();
+    let session = {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::Tee {
+                bytes: bytes.clone(),
+            })
+            .unwrap()
+            .stdout(&mut stdout)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(stdout, journal.bytes);
+    assert_eq!(journal.bytes[..4], 5u32.to_le_bytes());
+    assert_eq!(journal.bytes[4..], bytes);
+}
+
+#[test]
+fn write_slice_to() {
+    let words = vec![1u32, 2, 3, 0xffffffff];
+
+    let mut stderr: Vec<u8> = Vec::new();
+    {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::WriteSliceTo {
+                fd: fileno::STDERR,
+                words: words.clone(),
+            })
+            .unwrap()
+            .stderr(&mut stderr)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert!(session.journal.unwrap().bytes.is_empty());
+    }
+    assert_eq!(stderr, bytemuck::cast_slice::<u32, u8>(&words));
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::WriteSliceTo {
+            fd: fileno::JOURNAL,
+            words: words.clone(),
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(
+        session.journal.unwrap().bytes,
+        bytemuck::cast_slice::<u32, u8>(&words)
+    );
+}
+
+// Tests sys_read into a buffer of bytes that may not be word aligned.
+//
+// To make sure we don't miss any edge cases, this tries all permutations of
+// start alignment, end alignment, and 0, 1, or 2 whole words.
+#[test]
+fn posix_style_read() {
+    const FD: u32 = 123;
+    // Initial buffer to read bytes on top of.
+    let buf: Vec<u8> = (b'a'..=b'z')
+        .chain(b'0'..=b'9')
+        .chain(b"!@#$%^&*()".iter().cloned())
+        .collect();
+    // Input to read bytes from.
+    let readbuf: Vec<u8> = (b'A'..=b'Z').collect();
+
+    let run = |pos_and_len: Vec<(u32, u32)>| {
+        let mut expected = buf.to_vec();
+
+        let mut expected_readbuf = readbuf.as_slice();
+        for (pos, len) in pos_and_len.iter() {
+            let pos = *pos as usize;
+            let len = *len as usize;
+
+            let this_read;
+            (this_read, expected_readbuf) = expected_readbuf.split_at(len);
+            expected[pos..pos + len].clone_from_slice(this_read);
+        }
+
+        let spec = MultiTestSpec::SysRead {
+            fd: FD,
+            buf: buf.to_vec(),
+            pos_and_len: pos_and_len.clone(),
+        };
+        let env = ExecutorEnv::builder()
+            .read_fd(FD, readbuf.as_slice())
+            .write(&spec)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+        let session = exec.run().unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+        let (actual, num_read): (Vec<u8>, Vec<usize>) = session.journal.unwrap().decode().unwrap();
+        for ((_, len), n_read) in pos_and_len.iter().zip(num_read) {
+            assert_eq!(
+                *len as usize, n_read,
+                "length mismatch, pos and lens: {pos_and_len:?}"
+            )
+        }
+        assert_eq!(
+            from_utf8(&actual).unwrap(),
+            from_utf8(&expected).unwrap(),
+            "pos and lens: {pos_and_len:?}"
+        );
+    };
+
+    fn next_offset(mut pos: u32, offset: u32) -> u32 {
+        while (pos % WORD_SIZE as u32) != offset {
+            pos += 1;
+        }
+        pos
+    }
+
+    for start_offset in 0..WORD_SIZE as u32 {
+        for end_offset in 0..WORD_SIZE as u32 {
+            let mut pos = 0;
+            let mut pos_and_len: Vec<(u32, u32)> = Vec::new();
+
+            // Make up a bunch of reads to overwrite parts of the buffer.
+            for nwords in 0..3 {
+                pos = next_offset(pos, start_offset);
+                let start = pos;
+                pos += nwords * WORD_SIZE as u32;
+                pos = next_offset(pos, end_offset);
+                let len = pos - start;
+                pos_and_len.push((pos, len));
+                assert!(
+                    pos + len < buf.len() as u32,
+                    "Ran out of space to test writes. pos: {pos} len: {len} end: {end_offset} start = {start_offset}"
+                );
+                // Make sure there's at least one non-overwritten character between reads.
+                pos += 1;
+            }
+
+            run(pos_and_len);
+        }
+    }
+}
+
+#[test]
+fn read_buffered() {
+    let words: Vec<u32> = (0..20_000).collect();
+    let encoded = to_vec(&words).unwrap();
+    let len = (encoded.len() * WORD_SIZE) as u32;
+    for header in [len, u32::MAX] {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::ReadBuffered)
+            .unwrap()
+            .write_slice(&[header])
+            .write_slice(&encoded)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        let decoded: Vec<u32> = session.journal.unwrap().decode().unwrap();
+        assert_eq!(decoded, words);
+    }
+}
+
+#[test]
+fn stdin_peek_len() {
+    let spec = to_vec(&MultiTestSpec::StdinPeekLen).unwrap();
+
+    let env = ExecutorEnv::builder()
+        .write_slice(&spec)
+        .write_slice(b"abcde")
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let lens: (Option<u32>, Option<u32>) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(lens, (Some(5), Some(2)));
+
+    // The length of a streaming source is not known.
+    let mut input = bytemuck::cast_slice::<u32, u8>(&spec).to_vec();
+    input.extend_from_slice(b"abcde");
+    let env = ExecutorEnv::builder()
+        .stdin(input.as_slice())
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let lens: (Option<u32>, Option<u32>) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(lens, (None, None));
+}
+
+#[test]
+fn read_frame_endian() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadFrameEndian)
+        .unwrap()
+        .write_frame_be(b"big")
+        .write_frame_le(b"little")
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let (big, little): (Vec<u8>, Vec<u8>) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(big, b"big");
+    assert_eq!(little, b"little");
+}
+
+#[test]
+fn read_string() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadString)
+        .unwrap()
+        .write_frame("héllo".as_bytes())
+        .write_frame(&[b'a', 0xff, b'b'])
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let (valid, invalid): (Option<String>, Option<String>) =
+        session.journal.unwrap().decode().unwrap();
+    assert_eq!(valid.as_deref(), Some("héllo"));
+    assert_eq!(invalid, None);
+}
+
+#[test]
+fn expect_magic() {
+    let run = |header: &[u8]| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::ExpectMagic {
+                magic: b"RZ01".to_vec(),
+            })
+            .unwrap()
+            .write_slice(header)
+            .write(&42u32)
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session.journal.unwrap().decode::<(bool, u32)>().unwrap()
+    };
+
+    assert_eq!(run(b"RZ01"), (true, 42));
+    // A mismatched header does not consume the input that follows it.
+    assert_eq!(run(b"RZ02"), (false, 42));
+}
+
+#[test]
+fn read_delimited() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadDelimited {
+            delim: b'\n',
+            max_record: 8,
+        })
+        .unwrap()
+        .write_slice(b"first\n\nthird\nlast")
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    // The trailing record has no delimiter and is still returned.
+    let records: Vec<Result<Vec<u8>, String>> = session.journal.unwrap().decode().unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Ok(b"first".to_vec()),
+            Ok(vec![]),
+            Ok(b"third".to_vec()),
+            Ok(b"last".to_vec())
+        ]
+    );
+}
+
+#[test]
+fn read_delimited_too_long() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadDelimited {
+            delim: b'\n',
+            max_record: 4,
+        })
+        .unwrap()
+        .write_slice(b"abcd\nabcdef\nxy")
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    // The record that is too long is skipped, and reading continues after it.
+    let records: Vec<Result<Vec<u8>, String>> = session.journal.unwrap().decode().unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Ok(b"abcd".to_vec()),
+            Err("record exceeds maximum of 4 bytes".to_string()),
+            Ok(b"xy".to_vec())
+        ]
+    );
+}
+
+#[test]
+fn frames() {
+    let run = |builder: &mut ExecutorEnvBuilder| {
+        let session = ExecutorImpl::from_elf(builder.build().unwrap(), MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session
+            .journal
+            .unwrap()
+            .decode::<Vec<Result<bool, String>>>()
+            .unwrap()
+    };
+
+    // A frame that fails to deserialize is skipped, while a frame longer than
+    // the maximum ends the iteration.
+    let mut builder = ExecutorEnv::builder();
+    builder
+        .write(&MultiTestSpec::Frames { max: 4 })
+        .unwrap()
+        .write_frame(&1u32.to_le_bytes())
+        .write_frame(&2u32.to_le_bytes())
+        .write_frame(&0u32.to_le_bytes())
+        .write_frame(&[0u8; 8])
+        .write_frame(&1u32.to_le_bytes());
+    assert_eq!(
+        run(&mut builder),
+        vec![
+            Ok(true),
+            Err("failed to deserialize frame: Found a bool that wasn't 0 or 1".into()),
+            Ok(false),
+            Err("frame length 8 exceeds maximum of 4".into())
+        ]
+    );
+
+    // A payload shorter than its length header is an error.
+    let mut builder = ExecutorEnv::builder();
+    builder
+        .write(&MultiTestSpec::Frames { max: 16 })
+        .unwrap()
+        .write_frame(&1u32.to_le_bytes())
+        .write_slice(&8u32.to_le_bytes())
+        .write_slice(&[1u8; 4]);
+    assert_eq!(
+        run(&mut builder),
+        vec![
+            Ok(true),
+            Err("failed to deserialize frame: Unexpected end during deserialization".into())
+        ]
+    );
+}
+
+#[test]
+fn read_option() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ReadOption)
+        .unwrap()
+        .write(&Some(7u32))
+        .unwrap()
+        .write(&None::<u32>)
+        .unwrap()
+        .write(&Err::<u32, _>("error"))
+        .unwrap()
+        .write(&2u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let actual: (
+        Option<u32>,
+        Option<u32>,
+        Result<u32, String>,
+        Result<Option<u32>, String>,
+    ) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(
+        actual,
+        (
+            Some(7),
+            None,
+            Err("error".into()),
+            Err("Found an Option discriminant that wasn't 0 or 1".into())
+        )
+    );
+}
+
+#[test]
+fn presence_mask() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::PresenceMask { count: 3 })
+        .unwrap()
+        .write(&Some(5u32))
+        .unwrap()
+        .write(&None::<u32>)
+        .unwrap()
+        .write(&Some(6u32))
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes, [0b101, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(journal.read_presence_mask(0).unwrap(), 0b101);
+    assert!(journal.read_presence_mask(1).is_err());
+}
+
+#[test]
+fn read_pages() {
+    let run = |input: &[u8], expected_lens: &[usize]| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::ReadPages)
+            .unwrap()
+            .write_slice(input)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+        let (data, page_lens): (Vec<u8>, Vec<usize>) = session.journal.unwrap().decode().unwrap();
+        assert_eq!(data, input);
+        assert_eq!(page_lens, expected_lens);
+    };
+
+    run(&[], &[]);
+    run(&[7u8; 64], &[64]);
+    run(&(0..150).collect::<Vec<u8>>(), &[64, 64, 22]);
+}
+
+#[test]
+fn read_slice_exact() {
+    let run = |nwords: u32, input: &[u32]| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::ReadSliceExact { nwords })
+            .unwrap()
+            .write_slice(input)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session
+            .journal
+            .unwrap()
+            .decode::<(bool, Vec<u32>)>()
+            .unwrap()
+    };
+
+    assert_eq!(run(3, &[1, 2, 3]), (true, vec![1, 2, 3]));
+    // The host under-fills the slice.
+    assert!(!run(3, &[1, 2]).0);
+}
+
+#[test]
+fn journal_is_fresh() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::JournalIsFresh)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+
+    // Run until sys_pause
+    let session = exec.run().unwrap();
+    assert_eq!(session.exit_code, ExitCode::Paused(0));
+    let fresh: (bool, bool) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(fresh, (true, false));
+
+    // The resumed guest starts a new journal.
+    let session = exec.run().unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    let fresh: (bool, bool) = session.journal.unwrap().decode().unwrap();
+    assert_eq!(fresh, (true, false));
+}
+
+#[test]
+fn checkpoint() {
+    let mut stdout: Vec<u8> = Vec::new();
+    {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::Checkpoint)
+            .unwrap()
+            .stdout(&mut stdout)
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+    }
+
+    let (position, committed, resumed): (u32, u32, bool) =
+        crate::serde::from_slice(&stdout).unwrap();
+    assert_eq!((position, committed, resumed), (4, 8, false));
+}
+
+#[test]
+fn journal_bytes_hashed() {
+    let mut stdout: Vec<u8> = Vec::new();
+    let session = {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::JournalBytesHashed)
+            .unwrap()
+            .stdout(&mut stdout)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+
+    let hashed: u64 = crate::serde::from_slice(&stdout).unwrap();
+    assert_eq!(hashed, session.journal.unwrap().bytes.len() as u64);
+    assert_eq!(hashed, 108);
+}
+
+#[test]
+fn journal_position() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::JournalPosition)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    let (_, _, start, end): (u32, String, u32, u32) = journal.decode().unwrap();
+    assert_eq!((start, end), (4, 12));
+    let field: String =
+        crate::serde::from_slice(&journal.bytes[start as usize..end as usize]).unwrap();
+    assert_eq!(field, "abc");
+}
+
+#[test]
+fn commit_big_endian() {
+    let (a, b, c) = (0x01020304, 0x05060708090a0b0c, u128::MAX - 1);
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitBigEndian { a, b, c })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes.len(), 4 + 8 + 16);
+    assert_eq!(journal.bytes[..4], [1, 2, 3, 4]);
+    assert_eq!(journal.read_u32_be(0).unwrap(), a);
+    assert_eq!(journal.read_u64_be(4).unwrap(), b);
+    assert_eq!(journal.read_u128_be(12).unwrap(), c);
+    assert!(journal.read_u32_be(25).is_err());
+}
+
+#[test]
+fn array_round_trip() {
+    let words = [1u32, 2, 3, 4, 5, 6, 7, 0xffffffff];
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::ArrayRoundTrip)
+        .unwrap()
+        .write_slice(&words)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    // The journal holds exactly the array, with no length header.
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes, bytemuck::cast_slice::<u32, u8>(&words));
+    assert_eq!(
+        Digest::try_from(journal.bytes.as_slice()).unwrap(),
+        Digest::from(words)
+    );
+}
+
+#[test]
+fn cycle_markers() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CycleMarkers)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes.len(), 24);
+    let (first_label, first_cycles) = journal.read_cycle_marker(0).unwrap();
+    let (second_label, second_cycles) = journal.read_cycle_marker(12).unwrap();
+    assert_eq!((first_label, second_label), (1, 2));
+    assert!(first_cycles > 0);
+    assert!(second_cycles > first_cycles + 1000);
+    assert!(journal.read_cycle_marker(13).is_err());
+}
+
+#[test]
+fn commit_merkle_root() {
+    fn hash(prefix: u8, data: &[u8]) -> Digest {
+        Digest::try_from(
+            Sha256::new()
+                .chain_update([prefix])
+                .chain_update(data)
+                .finalize()
+                .as_slice(),
+        )
+        .unwrap()
+    }
+    fn run(leaf: &[u8], path: Vec<Digest>, root: Digest, index: u64) -> (Digest, bool) {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::MerkleRoot {
+                leaf: leaf.to_vec(),
+                path,
+                root,
+                index,
+            })
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session.journal.unwrap().decode().unwrap()
+    }
+
+    let left = hash(0x00, b"left");
+    let right = hash(0x00, b"right");
+    let mut children = left.as_bytes().to_vec();
+    children.extend_from_slice(right.as_bytes());
+    let root = hash(0x01, &children);
+
+    assert_eq!(run(b"left", vec![right], root, 0), (root, true));
+    assert_eq!(run(b"right", vec![left], root, 1), (root, true));
+    assert_eq!(run(b"right", vec![left], root, 0), (root, false));
+
+    // The concatenated children of the root are not accepted as a leaf.
+    assert_eq!(run(&children, vec![], root, 0), (root, false));
+}
+
+#[test]
+fn commit_cbor() {
+    let data = vec![1u8, 2, 3, 255];
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitCbor {
+            id: 42,
+            data: data.clone(),
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    let decoded: (u32, Vec<u8>) = ciborium::from_reader(&journal.bytes[..]).unwrap();
+    assert_eq!(decoded, (42, data));
+}
+
+#[test]
+fn commit_digests() {
+    let digests = vec![Digest::from([1u32; 8]), Digest::from([2u32; 8])];
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitDigests(digests.clone()))
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes.len(), 4 + 2 * DIGEST_BYTES);
+    assert_eq!(journal.read_digests(2).unwrap(), digests);
+    assert!(journal.read_digests(3).is_err());
+}
+
+#[test]
+fn aggregate_journal_digests() {
+    use crate::{guest::env::JOURNAL_AGGREGATION_TAG, sha::Sha256 as _};
+
+    let run = |digests: &[Digest]| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::AggregateJournalDigests(digests.to_vec()))
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session.journal.unwrap().bytes
+    };
+
+    // Recompute the aggregate from the documented construction.
+    let tag = crate::sha::Impl::hash_bytes(JOURNAL_AGGREGATION_TAG.as_bytes());
+    let digests = [Digest::from([1u32; 8]), Digest::from([2u32; 8])];
+    let expected = digests.iter().rev().fold(Digest::ZERO, |acc, digest| {
+        let mut bytes = tag.as_bytes().to_vec();
+        bytes.extend_from_slice(digest.as_bytes());
+        bytes.extend_from_slice(acc.as_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        *crate::sha::Impl::hash_bytes(&bytes)
+    });
+
+    let journal = run(&digests);
+    assert_eq!(&journal[..DIGEST_BYTES], expected.as_bytes());
+    let returned: Digest = crate::serde::from_slice(&journal[DIGEST_BYTES..]).unwrap();
+    assert_eq!(returned, expected);
+
+    let journal = run(&[]);
+    assert_eq!(&journal[..DIGEST_BYTES], Digest::ZERO.as_bytes());
+}
+
+#[test]
+fn commit_iter() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitIter { count: 5 })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let expected: Vec<(u32, u32)> = (0..5).map(|i| (i, i * i)).collect();
+    let journal = session.journal.unwrap();
+    let decoded: Vec<(u32, u32)> = journal.read_seq().collect::<Result<_, _>>().unwrap();
+    assert_eq!(decoded, expected);
+    let words = to_vec(&expected).unwrap();
+    assert_eq!(journal.bytes, bytemuck::cast_slice::<u32, u8>(&words));
+
+    // Values are decoded lazily, and a truncated journal yields an error for the last value.
+    let truncated = Journal::new(journal.bytes[..journal.bytes.len() - 4].to_vec());
+    let mut values = truncated.read_seq::<(u32, u32)>();
+    assert!(values.by_ref().take(4).all(|value| value.is_ok()));
+    assert!(values.next().unwrap().is_err());
+    assert!(values.next().is_none());
+    assert!(Journal::new(vec![])
+        .read_seq::<u32>()
+        .next()
+        .unwrap()
+        .is_err());
+}
+
+#[test]
+fn commit_versioned() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitVersioned)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let journal = session.journal.unwrap();
+    assert_eq!(&journal.bytes[..2], &3u16.to_le_bytes());
+    let (version, value): (u16, (u64, String)) = journal.read_versioned().unwrap();
+    assert_eq!(version, 3);
+    assert_eq!(value, (42, "hello".to_string()));
+    assert!(Journal::new(vec![3]).read_versioned::<u64>().is_err());
+}
+
+#[test]
+fn current_output() {
+    let mut stdout: Vec<u8> = Vec::new();
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CurrentOutput)
+        .unwrap()
+        .stdout(&mut stdout)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let expected = session.claim().unwrap().output.digest();
+    let actual: Digest = crate::serde::from_slice(&stdout).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn syscall_count() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::SyscallCount)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    // The first count query and both logs happen between the two queries.
+    let delta: u64 = session.journal.unwrap().decode().unwrap();
+    assert_eq!(delta, 3);
+}
+
+#[test]
+fn journal_digest() {
+    use crate::sha::rust_crypto::{Digest as _, Sha256};
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitBigEndian {
+            a: 0x01020304,
+            b: 0x05060708090a0b0c,
+            c: 0x0d0e0f101112131415161718191a1b1c,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    let journal = session.journal.clone().unwrap();
+    let expected = session
+        .claim()
+        .unwrap()
+        .output
+        .as_value()
+        .unwrap()
+        .as_ref()
+        .unwrap()
+        .journal
+        .digest();
+    assert_eq!(journal.digest(), expected);
+
+    let hashed: Digest = Sha256::digest(&journal.bytes)
+        .as_slice()
+        .try_into()
+        .unwrap();
+    assert_eq!(hashed, expected);
+}
+
+#[test]
+fn commit_large() {
+    use crate::{
+        guest::env::MAX_WRITE_CHUNK_BYTES,
+        sha::rust_crypto::{Digest as _, Sha256},
+    };
+
+    // Crosses the chunk boundary with a partial final chunk.
+    let len = MAX_WRITE_CHUNK_BYTES as u32 * 2 + 100;
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitLarge { len })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let expected_bytes: Vec<u8> = (0..len).map(|i| i as u8).collect();
+    let journal = session.journal.clone().unwrap();
+    assert_eq!(journal.bytes, expected_bytes);
 
-#[test]
-fn rsa_compat() {
-    run_test(MultiTestSpec::RsaCompat);
+    let expected: Digest = Sha256::digest(&expected_bytes)
+        .as_slice()
+        .try_into()
+        .unwrap();
+    let claimed = session
+        .claim()
+        .unwrap()
+        .output
+        .as_value()
+        .unwrap()
+        .as_ref()
+        .unwrap()
+        .journal
+        .digest();
+    assert_eq!(claimed, expected);
 }
 
 #[test]
-fn bigint_accel() {
-    let cases = testutils::generate_bigint_test_cases(&mut rand::thread_rng(), 10);
-    for case in cases {
-        println!("Running BigInt circuit test case: {:x?}", case);
-        let input = MultiTestSpec::BigInt {
-            x: case.x,
-            y: case.y,
-            modulus: case.modulus,
-        };
-
+fn cycle_budget() {
+    let run = |limit: u64| {
         let env = ExecutorEnv::builder()
-            .write(&input)
+            .write(&MultiTestSpec::CycleBudget { limit })
             .unwrap()
             .build()
             .unwrap();
-        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
-        let session = exec.run().unwrap();
-        assert_eq!(session.exit_code, ExitCode::Halted(0));
-        assert_eq!(
-            session.journal.unwrap().bytes.as_slice(),
-            bytemuck::cast_slice::<u32, u8>(case.expected().as_slice())
-        );
-    }
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+            .exit_code
+    };
+
+    assert_eq!(run(u64::MAX), ExitCode::Halted(0));
+    assert_eq!(
+        run(0),
+        ExitCode::Halted(crate::guest::env::CYCLE_BUDGET_EXIT_CODE as u32)
+    );
 }
 
 #[test]
-fn env_stdio() {
-    const MSG: &str = "Hello world!  This is a test of standard input and output.";
-    const FD: u32 = 123;
-    let spec = to_vec(&MultiTestSpec::EchoStdout { nbytes: 9, fd: FD }).unwrap();
-    let mut stdout: Vec<u8> = Vec::new();
-    {
+fn exit_with_status() {
+    for (status, exit_code) in [(0, 0), (1, 1), (0x12345678, 1)] {
         let env = ExecutorEnv::builder()
-            .read_fd(FD, MSG.as_bytes())
-            .stdin(bytemuck::cast_slice(&spec))
-            .stdout(&mut stdout)
+            .write(&MultiTestSpec::ExitWithStatus(status))
+            .unwrap()
             .build()
             .unwrap();
         let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
             .unwrap()
             .run()
             .unwrap();
-        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        assert_eq!(session.exit_code, ExitCode::Halted(exit_code));
+
+        let journal = session.journal.unwrap();
+        let (prefix, committed): (u32, u32) = journal.decode().unwrap();
+        assert_eq!(prefix, 7);
+        assert_eq!(committed, status);
     }
-    assert_eq!(MSG, from_utf8(&stdout).unwrap());
 }
 
-// Tests sys_read into a buffer of bytes that may not be word aligned.
-//
-// To make sure we don't miss any edge cases, this tries all permutations of
-// start alignment, end alignment, and 0, 1, or 2 whole words.
 #[test]
-fn posix_style_read() {
-    const FD: u32 = 123;
-    // Initial buffer to read bytes on top of.
-    let buf: Vec<u8> = (b'a'..=b'z')
-        .chain(b'0'..=b'9')
-        .chain(b"!@#$%^&*()".iter().cloned())
-        .collect();
-    // Input to read bytes from.
-    let readbuf: Vec<u8> = (b'A'..=b'Z').collect();
-
-    let run = |pos_and_len: Vec<(u32, u32)>| {
-        let mut expected = buf.to_vec();
-
-        let mut expected_readbuf = readbuf.as_slice();
-        for (pos, len) in pos_and_len.iter() {
-            let pos = *pos as usize;
-            let len = *len as usize;
-
-            let this_read;
-            (this_read, expected_readbuf) = expected_readbuf.split_at(len);
-            expected[pos..pos + len].clone_from_slice(this_read);
-        }
-
-        let spec = MultiTestSpec::SysRead {
-            fd: FD,
-            buf: buf.to_vec(),
-            pos_and_len: pos_and_len.clone(),
-        };
+fn ensure() {
+    let run = |holds: bool, with_message: bool| {
         let env = ExecutorEnv::builder()
-            .read_fd(FD, readbuf.as_slice())
-            .write(&spec)
+            .write(&MultiTestSpec::Ensure {
+                holds,
+                exit_code: 3,
+                with_message,
+            })
             .unwrap()
             .build()
             .unwrap();
-        let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
-        let session = exec.run().unwrap();
-        assert_eq!(session.exit_code, ExitCode::Halted(0));
-
-        let (actual, num_read): (Vec<u8>, Vec<usize>) = session.journal.unwrap().decode().unwrap();
-        for ((_, len), n_read) in pos_and_len.iter().zip(num_read) {
-            assert_eq!(
-                *len as usize, n_read,
-                "length mismatch, pos and lens: {pos_and_len:?}"
-            )
-        }
-        assert_eq!(
-            from_utf8(&actual).unwrap(),
-            from_utf8(&expected).unwrap(),
-            "pos and lens: {pos_and_len:?}"
-        );
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+            .exit_code
     };
 
-    fn next_offset(mut pos: u32, offset: u32) -> u32 {
-        while (pos % WORD_SIZE as u32) != offset {
-            pos += 1;
-        }
-        pos
-    }
-
-    for start_offset in 0..WORD_SIZE as u32 {
-        for end_offset in 0..WORD_SIZE as u32 {
-            let mut pos = 0;
-            let mut pos_and_len: Vec<(u32, u32)> = Vec::new();
-
-            // Make up a bunch of reads to overwrite parts of the buffer.
-            for nwords in 0..3 {
-                pos = next_offset(pos, start_offset);
-                let start = pos;
-                pos += nwords * WORD_SIZE as u32;
-                pos = next_offset(pos, end_offset);
-                let len = pos - start;
-                pos_and_len.push((pos, len));
-                assert!(
-                    pos + len < buf.len() as u32,
-                    "Ran out of space to test writes. pos: {pos} len: {len} end: {end_offset} start = {start_offset}"
-                );
-                // Make sure there's at least one non-overwritten character between reads.
-                pos += 1;
-            }
-
-            run(pos_and_len);
-        }
-    }
+    assert_eq!(run(true, true), ExitCode::Halted(0));
+    assert_eq!(run(true, false), ExitCode::Halted(0));
+    assert_eq!(run(false, true), ExitCode::Halted(3));
+    assert_eq!(run(false, false), ExitCode::Halted(3));
 }
 
 #[test]
@@ -516,8 +1488,10 @@ mod sys_verify {
     use test_log::test;
 
     use crate::{
-        serde::to_vec, sha::Digestible, ExecutorEnv, ExecutorEnvBuilder, ExecutorImpl, ExitCode,
-        MaybePruned, ReceiptClaim, Session,
+        serde::to_vec,
+        sha::{Digest, Digestible},
+        ExecutorEnv, ExecutorEnvBuilder, ExecutorImpl, ExitCode, MaybePruned, ReceiptClaim,
+        Session,
     };
 
     fn exec_hello_commit() -> Session {
@@ -591,6 +1565,43 @@ mod sys_verify {
             .is_err());
     }
 
+    #[test]
+    fn sys_verify_and_commit() {
+        let hello_commit_session = exec_hello_commit();
+        let hello_commit_journal = hello_commit_session.journal.clone().unwrap().bytes;
+
+        let run = |image_id: Digest, journal: Vec<u8>, assumption: ReceiptClaim| {
+            let spec = &MultiTestSpec::SysVerifyAndCommit { image_id, journal };
+            let env = ExecutorEnv::builder()
+                .write(&spec)
+                .unwrap()
+                .add_assumption(assumption)
+                .build()
+                .unwrap();
+            let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+                .unwrap()
+                .run()
+                .unwrap();
+            assert_eq!(session.exit_code, ExitCode::Halted(0));
+            assert_eq!(session.assumptions.len(), 1);
+            session.journal.unwrap().bytes
+        };
+
+        // The first level verifies the hello commit receipt and commits its journal digest.
+        let inner_journal = run(
+            HELLO_COMMIT_ID.into(),
+            hello_commit_journal.clone(),
+            hello_commit_session.claim().unwrap(),
+        );
+        assert_eq!(inner_journal, hello_commit_journal.digest().as_bytes());
+
+        // The second level verifies the first. Its assumption is the claim of the first level
+        // after its own assumption is resolved, as it would be in a succinct receipt.
+        let inner_claim = ReceiptClaim::ok(MULTI_TEST_ID, inner_journal.clone());
+        let outer_journal = run(MULTI_TEST_ID.into(), inner_journal.clone(), inner_claim);
+        assert_eq!(outer_journal, inner_journal.digest().as_bytes());
+    }
+
     #[test]
     fn sys_verify_halt_codes() {
         for code in [0u8, 1, 2, 255] {
@@ -648,6 +1659,40 @@ mod sys_verify {
             .is_err());
     }
 
+    #[test]
+    fn sys_verify_first_matching() {
+        let hello_commit_session = exec_hello_commit();
+        let claim = hello_commit_session.claim().unwrap();
+        let candidates = vec![
+            (Digest::from([1u32; 8]), Digest::ZERO),
+            (claim.digest(), Digest::ZERO),
+        ];
+
+        let run = |wanted: Digest| {
+            let spec = &MultiTestSpec::SysVerifyFirstMatching {
+                candidates: candidates.clone(),
+                wanted,
+            };
+            let env = ExecutorEnv::builder()
+                .write(&spec)
+                .unwrap()
+                .add_assumption(claim.clone())
+                .build()
+                .unwrap();
+            let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+                .unwrap()
+                .run()
+                .unwrap();
+            assert_eq!(session.exit_code, ExitCode::Halted(0));
+            let index: Option<u32> = session.journal.as_ref().unwrap().decode().unwrap();
+            (index, session.assumptions.len())
+        };
+
+        // Only the matching candidate is added to the assumptions.
+        assert_eq!(run(claim.digest()), (Some(1), 1));
+        assert_eq!(run(Digest::from([2u32; 8])), (None, 0));
+    }
+
     #[test]
     fn sys_verify_integrity_halt_codes() {
         for code in [0u8, 1, 2, 255] {
@@ -730,6 +1775,24 @@ mod sys_verify {
     }
 }
 
+#[test]
+fn env_sha256() {
+    for data in [Vec::new(), b"abc".to_vec(), vec![7u8; 1000]] {
+        let expected: Digest = Sha256::digest(&data).as_slice().try_into().unwrap();
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::Sha256 { data })
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        let actual: Digest = session.journal.unwrap().decode().unwrap();
+        assert_eq!(actual, expected);
+    }
+}
+
 #[test]
 fn large_sha() {
     let data = vec![0u8; 100_000];
@@ -926,6 +1989,31 @@ fn panic() {
     assert!(err.to_string().contains("MultiTestSpec::Panic invoked"));
 }
 
+#[test]
+fn panic_committer() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::PanicCommitter)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(
+        session.exit_code,
+        ExitCode::Halted(crate::guest::env::PANIC_EXIT_CODE as u32)
+    );
+
+    let (prefix, msg, location): (u32, String, Option<(String, u32)>) =
+        session.journal.unwrap().decode().unwrap();
+    assert_eq!(prefix, 1);
+    assert!(msg.contains("MultiTestSpec::PanicCommitter invoked"));
+    let (file, line) = location.unwrap();
+    assert!(file.ends_with("multi_test.rs"));
+    assert!(line > 0);
+}
+
 #[test]
 fn fault() {
     let env = ExecutorEnv::builder()
@@ -1159,11 +2247,55 @@ fn sys_fork() {
 }
 
 #[test]
-#[should_panic(expected = "Unknown syscall")]
+#[should_panic(expected = "fork depth limit")]
 fn sys_fork_fork_panic() {
     run_test(MultiTestSpec::SysForkFork);
 }
 
+#[test]
+fn run_unconstrained_inline() {
+    let run = |disable_fork: bool| {
+        let mut builder = ExecutorEnv::builder();
+        builder
+            .write(&MultiTestSpec::RunUnconstrainedInline)
+            .unwrap();
+        if disable_fork {
+            builder.disable_fork();
+        }
+        let session = ExecutorImpl::from_elf(builder.build().unwrap(), MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(session.exit_code, ExitCode::Halted(0));
+        session.journal.unwrap().decode::<bool>().unwrap()
+    };
+
+    // A forked child's changes to memory are discarded.
+    assert!(!run(false));
+    // Without fork support, the closure runs inline and its changes persist.
+    assert!(run(true));
+}
+
+#[test]
+fn run_unconstrained_nested() {
+    let mut stdout: Vec<u8> = Vec::new();
+    let session = {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::RunUnconstrainedNested)
+            .unwrap()
+            .stdout(&mut stdout)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(session.journal.unwrap().decode::<u32>().unwrap(), 42);
+    assert_eq!(from_utf8(&stdout).unwrap(), "inner;outer;parent;");
+}
+
 #[test]
 #[should_panic(expected = "Bad write file descriptor 3")]
 fn sys_fork_journal_panic() {
@@ -1199,6 +2331,60 @@ fn big_keccak() {
     run_test(MultiTestSpec::BigKeccak);
 }
 
+#[test]
+fn keccak_summary() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::KeccakSummary)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes.len(), 4 + 36);
+    let (digest, block_count) = crate::keccak::decode_summary(&journal.bytes[4..]).unwrap();
+    let expected = crate::keccak::expected_transcript_digest(&[b"abc", &[7u8; 300]]);
+    assert_eq!(digest, expected);
+    assert_eq!(block_count, 4);
+    assert!(crate::keccak::decode_summary(&journal.bytes[5..]).is_err());
+}
+
+#[test]
+fn keccak256() {
+    use sha3::{Digest as _, Keccak256};
+
+    use crate::guest::env::KeccakBatcher;
+
+    let run = |data: Vec<u8>| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::Keccak256 { data })
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        session
+            .journal
+            .unwrap()
+            .decode::<Result<[u8; 32], String>>()
+            .unwrap()
+    };
+
+    for data in [vec![], b"hello world".to_vec(), vec![7u8; 1000]] {
+        let expected: [u8; 32] = Keccak256::digest(&data).into();
+        assert_eq!(run(data).unwrap(), expected);
+    }
+
+    // An input that pads to more blocks than an entry can hold is an error.
+    let data = vec![7u8; KeccakBatcher::MAX_ENTRY_BLOCKS * KeccakBatcher::BLOCK_BYTES];
+    assert!(run(data).unwrap_err().contains("exceeds the limit"));
+}
+
 #[cfg(feature = "docker")]
 mod docker {
     use risc0_zkvm_methods::{multi_test::MultiTestSpec, MULTI_TEST_ELF};
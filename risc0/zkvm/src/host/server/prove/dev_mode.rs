@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Mutex;
+
 use anyhow::{bail, Result};
 
 use crate::{
@@ -19,7 +21,7 @@ use crate::{
     receipt::{FakeReceipt, InnerReceipt, SegmentReceipt, SuccinctReceipt},
     receipt_claim::Unknown,
     ExecutorEnv, ExecutorImpl, ProverOpts, ProverServer, Receipt, ReceiptClaim, Segment, Session,
-    VerifierContext,
+    SessionStats, VerifierContext,
 };
 
 /// An implementation of a [ProverServer] for development and testing purposes.
@@ -41,13 +43,85 @@ use crate::{
 /// It can be fully disabled at compile time, regardless of environment
 /// variables, by setting the feature flag `disable-dev-mode` on the
 /// `risc0_zkvm` crate.
-pub struct DevModeProver;
+///
+/// Construct it with [DevModeProver::new] or [Default::default], which write the
+/// dev mode warning to stderr.
+pub struct DevModeProver {
+    warning_sink: Box<dyn Fn(&str) + Send + Sync>,
+    last_session_stats: Mutex<Option<SessionStats>>,
+    expected_journal_len: Option<usize>,
+    segment_limit_po2: Option<u32>,
+}
+
+impl DevModeProver {
+    /// Construct a [DevModeProver] that writes its warning to stderr.
+    pub fn new() -> Self {
+        Self::with_warning_sink(|msg| eprintln!("{msg}"))
+    }
+
+    /// Construct a [DevModeProver] that sends its warning to the given sink.
+    ///
+    /// This can be used to route the warning to an application's logger. The sink must be
+    /// [Send] and [Sync], so that the prover can be shared across threads.
+    pub fn with_warning_sink(sink: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Self {
+            warning_sink: Box::new(sink),
+            last_session_stats: Mutex::new(None),
+            expected_journal_len: None,
+            segment_limit_po2: None,
+        }
+    }
+
+    /// Construct a [DevModeProver] that does not emit any warning.
+    pub fn silent() -> Self {
+        Self::with_warning_sink(|_| {})
+    }
+
+    /// Require the guest to commit a journal of at least `min` bytes.
+    ///
+    /// Proving a [Session] with a shorter journal returns an error instead of a fake receipt.
+    /// This catches a guest that forgot to commit its output early in the development loop.
+    pub fn with_expected_journal_len(self, min: usize) -> Self {
+        Self {
+            expected_journal_len: Some(min),
+            ..self
+        }
+    }
+
+    /// Execute with a segment limit of `2^po2` cycles, overriding the limit set on the
+    /// [ExecutorEnv].
+    ///
+    /// A small limit splits the session into many segments, as in real proving, so that
+    /// multi-segment behavior such as pause and resume is exercised in dev mode. This only
+    /// applies to proofs from an ELF binary; a [Session] passed to
+    /// [prove_session][ProverServer::prove_session] has already been segmented.
+    pub fn with_segment_limit(self, po2: u32) -> Self {
+        Self {
+            segment_limit_po2: Some(po2),
+            ..self
+        }
+    }
+
+    /// Return the [SessionStats] of the most recently proven [Session], if any.
+    ///
+    /// These are the same statistics the real prover reports, which makes dev mode useful to
+    /// estimate proving cost without generating a proof.
+    pub fn last_session_stats(&self) -> Option<SessionStats> {
+        self.last_session_stats.lock().unwrap().clone()
+    }
+}
+
+impl Default for DevModeProver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ProverServer for DevModeProver {
     fn prove_session(&self, _ctx: &VerifierContext, session: &Session) -> Result<ProveInfo> {
-        eprintln!(
+        (self.warning_sink)(
             "WARNING: Proving in dev mode does not generate a valid receipt. \
-            Receipts generated from this process are invalid and should never be used in production."
+            Receipts generated from this process are invalid and should never be used in production.",
         );
 
         if cfg!(feature = "disable-dev-mode") {
@@ -56,6 +130,16 @@ impl ProverServer for DevModeProver {
             )
         }
 
+        if let Some(min) = self.expected_journal_len {
+            let len = session
+                .journal
+                .as_ref()
+                .map_or(0, |journal| journal.bytes.len());
+            if len < min {
+                bail!("zkVM: dev mode expected a journal of at least {min} bytes, but the guest committed {len} bytes")
+            }
+        }
+
         let claim = session.claim()?;
         let receipt = Receipt::new(
             InnerReceipt::Fake(FakeReceipt {
@@ -64,19 +148,22 @@ impl ProverServer for DevModeProver {
             session.journal.clone().unwrap_or_default().bytes,
         );
 
-        Ok(ProveInfo {
-            receipt,
-            stats: session.stats(),
-        })
+        let stats = session.stats();
+        *self.last_session_stats.lock().unwrap() = Some(stats.clone());
+
+        Ok(ProveInfo { receipt, stats })
     }
 
     /// Prove the specified ELF binary using the specified [VerifierContext].
     fn prove_with_ctx(
         &self,
-        env: ExecutorEnv<'_>,
+        mut env: ExecutorEnv<'_>,
         ctx: &VerifierContext,
         elf: &[u8],
     ) -> Result<ProveInfo> {
+        if let Some(po2) = self.segment_limit_po2 {
+            env.segment_limit_po2 = Some(po2);
+        }
         let mut exec = ExecutorImpl::from_elf(env, elf)?;
         let session = exec.run_with_callback(null_callback)?;
         self.prove_session(ctx, &session)
@@ -26,7 +26,7 @@ use risc0_circuit_rv32im::prove::segment_prover;
 use risc0_core::field::baby_bear::{BabyBear, Elem, ExtElem};
 use risc0_zkp::hal::{CircuitHal, Hal};
 
-use self::{dev_mode::DevModeProver, prover_impl::ProverImpl};
+use self::prover_impl::ProverImpl;
 use crate::{
     host::prove_info::ProveInfo,
     is_dev_mode,
@@ -40,6 +40,8 @@ use crate::{
     Segment, Session, VerifierContext,
 };
 
+pub use self::dev_mode::DevModeProver;
+
 /// A ProverServer can execute a given ELF binary and produce a [ProveInfo] which contains a
 /// [Receipt][crate::Receipt] that can be used to verify correct computation.
 pub trait ProverServer {
@@ -231,12 +233,32 @@ impl Session {
 
 /// Select a [ProverServer] based on the specified [ProverOpts] and currently
 /// compiled features.
+///
+/// When dev mode is enabled by the `RISC0_DEV_MODE` environment variable, this returns a
+/// [DevModeProver]. If the `disable-dev-mode` feature is set, the environment variable is
+/// rejected by [is_dev_mode] instead of silently producing fake receipts. Otherwise, a prover
+/// generating real proofs for [ProverOpts::hashfn] is returned.
 pub fn get_prover_server(opts: &ProverOpts) -> Result<Rc<dyn ProverServer>> {
+    select_prover_server(opts).map(Rc::from)
+}
+
+/// Select a boxed [ProverServer] with the default [ProverOpts].
+///
+/// This is the same as calling [get_prover_server] with [ProverOpts::default], but returns a
+/// [Box] for callers that need ownership of the prover. Both share one selection, so a
+/// [DevModeProver] is returned exactly when [get_prover_server] would return one.
+pub fn default_prover_server() -> Result<Box<dyn ProverServer>> {
+    select_prover_server(&ProverOpts::default())
+}
+
+// The single place the prover is selected; the public functions above only
+// wrap its result.
+fn select_prover_server(opts: &ProverOpts) -> Result<Box<dyn ProverServer>> {
     if is_dev_mode() {
         eprintln!("WARNING: proving in dev mode. This will not generate valid, secure proofs.");
-        return Ok(Rc::new(DevModeProver));
+        return Ok(Box::new(DevModeProver::new()));
     }
 
     let prover = segment_prover(&opts.hashfn)?;
-    Ok(Rc::new(ProverImpl::new(opts.clone(), prover)))
+    Ok(Box::new(ProverImpl::new(opts.clone(), prover)))
 }
@@ -20,10 +20,11 @@ use risc0_zkvm_methods::{multi_test::MultiTestSpec, MULTI_TEST_ELF, MULTI_TEST_I
 use risc0_zkvm_platform::{memory, PAGE_SIZE, WORD_SIZE};
 use test_log::test;
 
-use super::get_prover_server;
+use super::{default_prover_server, get_prover_server};
 use crate::{
     host::server::testutils,
     serde::{from_slice, to_vec},
+    sha::Digestible,
     ExecutorEnv, ExecutorImpl, ExitCode, ProveInfo, ProverOpts, Receipt, Session, VerifierContext,
 };
 
@@ -45,6 +46,22 @@ fn prove_nothing(hashfn: &str) -> Result<ProveInfo> {
     get_prover_server(&opts).unwrap().prove(env, MULTI_TEST_ELF)
 }
 
+#[test]
+fn default_prover_server_proves() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .build()
+        .unwrap();
+    default_prover_server()
+        .unwrap()
+        .prove(env, MULTI_TEST_ELF)
+        .unwrap()
+        .receipt
+        .verify(MULTI_TEST_ID)
+        .unwrap();
+}
+
 #[test]
 fn prove_nothing_succinct() {
     let env = ExecutorEnv::builder()
@@ -63,6 +80,37 @@ fn prove_nothing_succinct() {
         .unwrap(); // ensure that we got a succinct receipt.
 }
 
+#[test]
+fn succinct_verify_claim_digest() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .build()
+        .unwrap();
+    let receipt = get_prover_server(&ProverOpts::succinct())
+        .unwrap()
+        .prove(env, MULTI_TEST_ELF)
+        .unwrap()
+        .receipt
+        .inner
+        .succinct()
+        .unwrap()
+        .clone()
+        .into_unknown();
+
+    let claim = receipt.claim.digest();
+    receipt.verify_claim_digest(&claim).unwrap();
+
+    let wrong = Digest::from([1u32; 8]);
+    assert_eq!(
+        receipt.verify_claim_digest(&wrong).unwrap_err(),
+        VerificationError::ClaimDigestMismatch {
+            expected: wrong,
+            received: claim,
+        }
+    );
+}
+
 #[test]
 fn hashfn_poseidon2() {
     prove_nothing("poseidon2").unwrap();
@@ -555,7 +603,7 @@ mod docker {
             receipt.clone().journal.bytes,
         );
 
-        let prover = DevModeProver;
+        let prover = DevModeProver::silent();
         let receipt = prover.compress(&ProverOpts::composite(), &fake).unwrap();
         ensure_fake(receipt);
         let receipt = prover.compress(&ProverOpts::succinct(), &fake).unwrap();
@@ -1055,6 +1103,123 @@ fn run_unconstrained() -> Result<()> {
     Ok(())
 }
 
+mod dev_mode {
+    use risc0_zkvm_methods::{
+        multi_test::MultiTestSpec, HELLO_COMMIT_ELF, HELLO_COMMIT_ID, MULTI_TEST_ELF,
+    };
+    use test_log::test;
+
+    use crate::{
+        host::server::prove::DevModeProver,
+        sha::{Digest, Digestible},
+        Assumption, Assumptions, ExecutorEnv, ExecutorImpl, ProverServer,
+    };
+
+    #[test]
+    fn session_stats() {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::DoNothing)
+            .unwrap()
+            .build()
+            .unwrap();
+        let prover = DevModeProver::silent();
+        assert!(prover.last_session_stats().is_none());
+
+        let info = prover.prove(env, MULTI_TEST_ELF).unwrap();
+        let stats = prover.last_session_stats().unwrap();
+        assert_eq!(stats.segments, info.stats.segments);
+        assert_eq!(stats.total_cycles, info.stats.total_cycles);
+        assert_eq!(stats.user_cycles, info.stats.user_cycles);
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let prover = std::sync::Arc::new(DevModeProver::silent());
+        let handle = {
+            let prover = prover.clone();
+            std::thread::spawn(move || {
+                let env = ExecutorEnv::builder()
+                    .write(&MultiTestSpec::DoNothing)
+                    .unwrap()
+                    .build()
+                    .unwrap();
+                prover.prove(env, MULTI_TEST_ELF).unwrap().stats
+            })
+        };
+        let stats = handle.join().unwrap();
+        assert_eq!(
+            prover.last_session_stats().unwrap().total_cycles,
+            stats.total_cycles
+        );
+    }
+
+    #[test]
+    fn expected_journal_len() {
+        let prove = |spec: &MultiTestSpec, min: usize| {
+            let env = ExecutorEnv::builder().write(spec).unwrap().build().unwrap();
+            DevModeProver::silent()
+                .with_expected_journal_len(min)
+                .prove(env, MULTI_TEST_ELF)
+        };
+
+        let spec = MultiTestSpec::ShaDigest {
+            data: vec![1, 2, 3],
+        };
+        assert!(prove(&spec, 32).is_ok());
+        assert!(prove(&spec, 33).is_err());
+        assert!(prove(&MultiTestSpec::DoNothing, 0).is_ok());
+        assert!(prove(&MultiTestSpec::DoNothing, 1).is_err());
+    }
+
+    #[test]
+    fn claim_includes_assumptions() {
+        let hello_commit = ExecutorImpl::from_elf(ExecutorEnv::default(), HELLO_COMMIT_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        let hello_commit_claim = hello_commit.claim().unwrap();
+
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::SysVerify(vec![(
+                HELLO_COMMIT_ID.into(),
+                hello_commit.journal.unwrap().bytes,
+            )]))
+            .unwrap()
+            .add_assumption(hello_commit_claim.clone())
+            .build()
+            .unwrap();
+        let receipt = DevModeProver::silent()
+            .prove(env, MULTI_TEST_ELF)
+            .unwrap()
+            .receipt;
+
+        // The unresolved assumption is carried in the claim, as in a conditional receipt.
+        let expected = Assumptions(vec![Assumption {
+            claim: hello_commit_claim.digest(),
+            control_root: Digest::ZERO,
+        }
+        .into()]);
+        let claim = receipt.claim().unwrap().value().unwrap();
+        let output = claim.output.value().unwrap().unwrap();
+        assert_eq!(output.assumptions.digest(), expected.digest());
+    }
+
+    #[test]
+    fn segment_limit() {
+        let prove = |prover: DevModeProver| {
+            let env = ExecutorEnv::builder()
+                .write(&MultiTestSpec::BusyLoop { cycles: 1 << 16 })
+                .unwrap()
+                .build()
+                .unwrap();
+            prover.prove(env, MULTI_TEST_ELF).unwrap().stats.segments
+        };
+
+        assert_eq!(prove(DevModeProver::silent()), 1);
+        assert!(prove(DevModeProver::silent().with_segment_limit(14)) > 1);
+    }
+}
+
 mod soundness {
     use risc0_circuit_rv32im::{prove::emu::exec::DEFAULT_SEGMENT_LIMIT_PO2, CIRCUIT};
     use risc0_zkp::{
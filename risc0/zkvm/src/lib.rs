@@ -80,6 +80,8 @@ mod receipt_claim;
 pub mod serde;
 pub mod sha;
 
+#[cfg(all(not(target_os = "zkvm"), feature = "std", feature = "unstable"))]
+pub use host::keccak;
 #[cfg(all(not(target_os = "zkvm"), feature = "prove"))]
 pub use host::recursion;
 
@@ -106,7 +108,9 @@ pub use {
         },
         server::{
             exec::executor::ExecutorImpl,
-            prove::{get_prover_server, HalPair, ProverServer},
+            prove::{
+                default_prover_server, get_prover_server, DevModeProver, HalPair, ProverServer,
+            },
             session::{
                 FileSegmentRef, NullSegmentRef, Segment, SegmentRef, Session, SessionEvents,
                 SimpleSegmentRef,
@@ -136,6 +140,7 @@ pub use {
                 default_executor, default_prover, external::ExternalProver, Executor, Prover,
                 ProverOpts, ReceiptKind,
             },
+            slice_io::SliceIoBuilder,
         },
     },
     risc0_circuit_rv32im::trace::{TraceCallback, TraceEvent},
@@ -145,8 +150,8 @@ pub use {
 #[cfg(feature = "client")]
 #[cfg(feature = "unstable")]
 pub use self::host::client::env::{
-    CoprocessorCallback, KeccakCoprocessorCallback, ProveKeccakRequest, ProveKeccakResponse,
-    ProveZkrRequest,
+    CoprocessorCallback, KeccakCoprocessorCallback, ProveKeccakRequest, ProveKeccakRequestBuilder,
+    ProveKeccakResponse, ProveZkrRequest,
 };
 
 #[cfg(not(target_os = "zkvm"))]
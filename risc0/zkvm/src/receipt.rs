@@ -41,8 +41,8 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 // Make succinct receipt available through this `receipt` module.
 use crate::{
     receipt_claim::Unknown,
-    serde::{from_slice, Error},
-    sha::{Digestible, Sha256},
+    serde::{from_slice, Deserializer, Error},
+    sha::{Digestible, Sha256, DIGEST_BYTES},
     Assumption, Assumptions, MaybePruned, Output, ReceiptClaim,
 };
 
@@ -257,6 +257,52 @@ impl Receipt {
         self.inner.claim()
     }
 
+    /// Encode the claim digest of this receipt as the two Groth16 public inputs that commit to it.
+    ///
+    /// The journal is not a public input of the Groth16 verifier on its own. It is bound through
+    /// the claim digest, and there is no separate BN254 encoding of the journal:
+    ///
+    /// 1. The journal digest is the SHA-256 digest of the journal bytes, i.e. the [Digestible]
+    ///    digest of the [Journal]. This is the value an application passes to an on-chain
+    ///    verifier along with the seal and image ID.
+    /// 2. The journal digest is part of the [Output] of the [ReceiptClaim], and the claim is
+    ///    hashed with SHA-256 into the claim digest.
+    /// 3. The claim digest is split into two 128-bit halves, each used as a BN254 scalar public
+    ///    input, as done by `risc0_groth16::split_digest`. The first half is the first 16 bytes
+    ///    of the digest read as a little-endian integer, and the second is the last 16 bytes.
+    ///
+    /// Each half is returned as the 32 byte big-endian encoding of the scalar, i.e. the `uint256`
+    /// an EVM verifier uses. The remaining public inputs, the control root split the same way and
+    /// the BN254 control ID, come from the verifier parameters rather than the receipt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::{sha::Digest, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    ///
+    /// let image_id = Digest::from([1u32, 2, 3, 4, 5, 6, 7, 8]);
+    /// let journal = b"hello".to_vec();
+    ///
+    /// // The claim commits to the SHA-256 digest of the journal.
+    /// let claim = ReceiptClaim::ok(image_id, journal.clone());
+    /// let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal);
+    /// let [claim0, claim1] = receipt.claim_digest_bn254().unwrap();
+    ///
+    /// // Each half of the claim digest is a 128-bit scalar.
+    /// assert_eq!(claim0[..16], [0u8; 16]);
+    /// assert_eq!(claim1[..16], [0u8; 16]);
+    /// ```
+    pub fn claim_digest_bn254(&self) -> Result<[[u8; 32]; 2], VerificationError> {
+        let claim_digest = self.claim()?.digest();
+        let (low, high) = claim_digest.as_bytes().split_at(DIGEST_BYTES / 2);
+        Ok([low, high].map(|half| {
+            let mut scalar = [0u8; 32];
+            scalar[16..].copy_from_slice(half);
+            scalar[16..].reverse();
+            scalar
+        }))
+    }
+
     /// Total number of bytes used by the seals of this receipt.
     pub fn seal_size(&self) -> usize {
         self.inner.seal_size()
@@ -286,8 +332,124 @@ impl Journal {
     pub fn decode<T: DeserializeOwned>(&self) -> Result<T, Error> {
         from_slice(&self.bytes)
     }
+
+    /// Lazily decode a journal written by `env::commit_iter` in the guest.
+    ///
+    /// The journal is expected to start with a `u32` count, followed by that many values encoded
+    /// with the [risc0 serializer](crate::serde). Each value is decoded as the iterator is
+    /// advanced, and the iterator ends after the first error. Collecting the iterator into a
+    /// `Result<Vec<T>, Error>` is the same as decoding a [Vec].
+    pub fn read_seq<T: DeserializeOwned>(&self) -> impl Iterator<Item = Result<T, Error>> {
+        let words = bytemuck::allocation::pod_collect_to_vec::<u8, u32>(&self.bytes);
+        let (mut remaining, mut pos, mut error) = match words.first() {
+            Some(&count) => (count, 1, None),
+            None => (0, 0, Some(Error::DeserializeUnexpectedEnd)),
+        };
+        core::iter::from_fn(move || {
+            if let Some(err) = error.take() {
+                return Some(Err(err));
+            }
+            if remaining == 0 {
+                return None;
+            }
+            let mut rest = &words[pos..];
+            let result = T::deserialize(&mut Deserializer::new(&mut rest));
+            pos = words.len() - rest.len();
+            remaining = if result.is_ok() { remaining - 1 } else { 0 };
+            Some(result)
+        })
+    }
+
+    /// Decode a journal written by `env::commit_versioned` in the guest.
+    ///
+    /// The journal is expected to start with a 2-byte little-endian version,
+    /// followed by the value encoded with the [risc0 serializer](crate::serde).
+    /// Returns both the version and the decoded value.
+    pub fn read_versioned<T: DeserializeOwned>(&self) -> Result<(u16, T), Error> {
+        if self.bytes.len() < 2 {
+            return Err(Error::DeserializeUnexpectedEnd);
+        }
+        let (version, value) = self.bytes.split_at(2);
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        Ok((version, from_slice(value)?))
+    }
+
+    /// Read a big-endian `u32` written by `env::commit_u32_be` at the given byte offset.
+    pub fn read_u32_be(&self, offset: usize) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.read_array(offset)?))
+    }
+
+    /// Read a big-endian `u64` written by `env::commit_u64_be` at the given byte offset.
+    pub fn read_u64_be(&self, offset: usize) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(self.read_array(offset)?))
+    }
+
+    /// Read a big-endian `u128` written by `env::commit_u128_be` at the given byte offset.
+    pub fn read_u128_be(&self, offset: usize) -> Result<u128, Error> {
+        Ok(u128::from_be_bytes(self.read_array(offset)?))
+    }
+
+    /// Read a presence bitmask written by `env::commit_presence_mask` at the given byte offset.
+    ///
+    /// Bit `i` of the mask, counting from the least significant bit, is set if the `i`-th optional
+    /// input of the guest was present.
+    pub fn read_presence_mask(&self, offset: usize) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.read_array(offset)?))
+    }
+
+    /// Read a marker written by `env::commit_cycle_marker` at the given byte offset.
+    ///
+    /// Returns the label ID and the cycle count of the marker. Each marker is 12 bytes long, so
+    /// consecutive markers are at offsets 12 bytes apart. The cycle count was reported by the host
+    /// to the guest and is not checked by the zkVM circuit.
+    pub fn read_cycle_marker(&self, offset: usize) -> Result<(u32, u64), Error> {
+        let marker: [u8; 12] = self.read_array(offset)?;
+        let (label_id, cycles) = marker.split_at(4);
+        Ok((
+            u32::from_le_bytes(label_id.try_into().unwrap()),
+            u64::from_le_bytes(cycles.try_into().unwrap()),
+        ))
+    }
+
+    fn read_array<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        self.bytes
+            .get(offset..)
+            .and_then(|bytes| bytes.get(..N))
+            .map(|bytes| bytes.try_into().unwrap())
+            .ok_or(Error::DeserializeUnexpectedEnd)
+    }
+
+    /// Decode a journal written by `env::commit_digests` in the guest.
+    ///
+    /// The journal is expected to start with a 4-byte little-endian count equal
+    /// to `n`, followed by `n` digests of 32 bytes each.
+    pub fn read_digests(&self, n: usize) -> Result<Vec<Digest>, Error> {
+        if self.bytes.len() < 4 {
+            return Err(Error::DeserializeUnexpectedEnd);
+        }
+        let (len, records) = self.bytes.split_at(4);
+        let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+        if len != n {
+            return Err(Error::Custom(alloc::format!(
+                "expected {n} digests, journal contains {len}"
+            )));
+        }
+        if records.len() < n * DIGEST_BYTES {
+            return Err(Error::DeserializeUnexpectedEnd);
+        }
+        Ok(records
+            .chunks_exact(DIGEST_BYTES)
+            .take(n)
+            .map(|record| Digest::try_from(record).unwrap())
+            .collect())
+    }
 }
 
+/// The digest of a [Journal] is the SHA-256 hash of its bytes, with no framing or padding.
+///
+/// This matches the digest computed by the guest as it commits to the journal, which hashes each
+/// write with [rust_crypto::Sha256][crate::sha::rust_crypto::Sha256], so the journal digest in a
+/// [ReceiptClaim] can be computed on the host from the journal bytes alone.
 impl risc0_binfmt::Digestible for Journal {
     fn digest<S: Sha256>(&self) -> Digest {
         *S::hash_bytes(&self.bytes)
@@ -796,11 +958,25 @@ impl Default for VerifierContext {
 mod tests {
     use super::{FakeReceipt, InnerReceipt, Receipt};
     use crate::{
-        sha::{Digest, DIGEST_BYTES},
-        MaybePruned,
+        sha::{Digest, Digestible, DIGEST_BYTES},
+        MaybePruned, ReceiptClaim,
     };
     use risc0_zkp::verify::VerificationError;
 
+    #[test]
+    fn claim_digest_bn254_matches_groth16_split() {
+        let journal = b"journal".to_vec();
+        let claim = ReceiptClaim::ok(Digest::from([1u32, 2, 3, 4, 5, 6, 7, 8]), journal.clone());
+        let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim.clone())), journal);
+
+        let (claim0, claim1) = risc0_groth16::split_digest(claim.digest()).unwrap();
+        let halves = receipt.claim_digest_bn254().unwrap();
+        for (half, expected) in halves.iter().zip([claim0, claim1]) {
+            let scalar = risc0_groth16::fr_from_hex_string(&hex::encode(half)).unwrap();
+            assert_eq!(scalar.digest(), expected.digest());
+        }
+    }
+
     #[test]
     fn mangled_version_info_should_error() {
         let mut mangled_receipt = Receipt::new(
@@ -841,6 +1017,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn journal_read_versioned() {
+        use crate::{serde::to_vec, Journal};
+
+        let value: (u32, Vec<u8>) = (7, vec![1, 2, 3]);
+        let mut bytes = 3u16.to_le_bytes().to_vec();
+        bytes.extend_from_slice(bytemuck::cast_slice(&to_vec(&value).unwrap()));
+
+        let journal = Journal::new(bytes);
+        assert_eq!(journal.read_versioned().unwrap(), (3u16, value));
+        assert!(Journal::new(vec![1]).read_versioned::<u32>().is_err());
+    }
+
     #[test]
     fn borsh_serde() {
         use crate::ReceiptClaim;
@@ -100,6 +100,24 @@ where
         self.verify_integrity_with_context(&VerifierContext::default())
     }
 
+    /// Verify the integrity of this receipt, ensuring the claim is attested
+    /// to by the seal and that its digest is `expected_claim`.
+    ///
+    /// This is useful for a receipt with an opaque claim, such as one with an
+    /// [Unknown] claim, that is checked against a claim digest computed
+    /// independently of the prover. The claim digest is compared first, so a
+    /// receipt for the wrong claim is rejected without verifying its seal.
+    pub fn verify_claim_digest(&self, expected_claim: &Digest) -> Result<(), VerificationError> {
+        let received = self.claim.digest::<sha::Impl>();
+        if received != *expected_claim {
+            return Err(VerificationError::ClaimDigestMismatch {
+                expected: *expected_claim,
+                received,
+            });
+        }
+        self.verify_integrity()
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context(
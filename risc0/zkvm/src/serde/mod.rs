@@ -44,13 +44,14 @@ mod serializer;
 
 pub use deserializer::{from_slice, Deserializer, WordRead};
 pub use err::{Error, Result};
-pub use serializer::{to_vec, to_vec_with_capacity, Serializer, WordWrite};
+pub use serializer::{to_vec, to_vec_canonical, to_vec_with_capacity, Serializer, WordWrite};
 
 #[cfg(test)]
 mod tests {
-    use crate::serde::{from_slice, to_vec};
+    use crate::serde::{from_slice, to_vec, to_vec_canonical};
     use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
     use chrono::NaiveDate;
+    use std::collections::HashMap;
 
     #[test]
     fn test_vec_round_trip() {
@@ -84,4 +85,35 @@ mod tests {
         let output: NaiveDate = from_slice(date_vec.as_slice()).unwrap();
         assert_eq!(input, output);
     }
+
+    #[test]
+    fn canonical_map_is_stable() {
+        let entries: Vec<(u32, Vec<u32>)> = (0..100u32).map(|i| (i * 37 % 101, vec![i])).collect();
+        let forward: HashMap<u32, Vec<u32>> = entries.iter().cloned().collect();
+        let mut reverse: HashMap<u32, Vec<u32>> = HashMap::with_capacity(1000);
+        reverse.extend(entries.iter().rev().cloned());
+
+        let data = to_vec_canonical(&forward).unwrap();
+        assert_eq!(data, to_vec_canonical(&reverse).unwrap());
+
+        // Keys of up to 32 bits sort numerically, matching an ordered map.
+        let ordered: BTreeMap<u32, Vec<u32>> = entries.into_iter().collect();
+        assert_eq!(data, to_vec(&ordered).unwrap());
+        let output: HashMap<u32, Vec<u32>> = from_slice(data.as_slice()).unwrap();
+        assert_eq!(output, forward);
+    }
+
+    #[test]
+    fn canonical_nested_map_is_stable() {
+        let inner = |keys: &[&str]| -> HashMap<String, u32> {
+            keys.iter()
+                .map(|key| (String::from(*key), key.len() as u32))
+                .collect()
+        };
+        let a: HashMap<u32, HashMap<String, u32>> =
+            HashMap::from([(1, inner(&["x", "yy", "zzz"])), (2, inner(&["abc", "d"]))]);
+        let b: HashMap<u32, HashMap<String, u32>> =
+            HashMap::from([(2, inner(&["d", "abc"])), (1, inner(&["zzz", "yy", "x"]))]);
+        assert_eq!(to_vec_canonical(&a).unwrap(), to_vec_canonical(&b).unwrap());
+    }
 }
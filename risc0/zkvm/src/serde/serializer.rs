@@ -93,9 +93,23 @@ where
     Ok(vec)
 }
 
+/// Serialize to a vector of u32 words in canonical form
+///
+/// See [Serializer::new_canonical] for the canonicalization rules.
+pub fn to_vec_canonical<T>(value: &T) -> Result<Vec<u32>>
+where
+    T: serde::Serialize + ?Sized,
+{
+    let mut vec: Vec<u32> = Vec::with_capacity(core::mem::size_of_val(value));
+    let mut serializer = Serializer::new_canonical(&mut vec);
+    value.serialize(&mut serializer)?;
+    Ok(vec)
+}
+
 /// Enables serializing to a stream
 pub struct Serializer<W: WordWrite> {
     stream: W,
+    canonical: bool,
 }
 
 impl<W: WordWrite> Serializer<W> {
@@ -103,7 +117,35 @@ impl<W: WordWrite> Serializer<W> {
     ///
     /// Creates a serializer that writes to `stream`.
     pub fn new(stream: W) -> Self {
-        Serializer { stream }
+        Serializer {
+            stream,
+            canonical: false,
+        }
+    }
+
+    /// Construct a Serializer that produces canonical output
+    ///
+    /// Creates a serializer that writes to `stream`, producing the same words
+    /// for equal values regardless of the iteration order of any maps they
+    /// contain. The canonicalization rules are:
+    ///
+    /// * Map entries are sorted by the serialized words of their keys, compared
+    ///   lexicographically. For integer keys of up to 32 bits this is numeric
+    ///   order. Other keys sort by their encoding, e.g. strings sort by length
+    ///   before content.
+    /// * Struct fields, tuples, and sequences are written in the order given by
+    ///   their `Serialize` impl, which is already deterministic for derived
+    ///   impls. Sequences are not reordered, so unordered sets such as
+    ///   `HashSet` are not canonicalized.
+    ///
+    /// Apart from the order of map entries, the output is identical to that of
+    /// [Serializer::new], so it can be decoded with [from_slice][super::from_slice].
+    /// Each map is buffered in memory before it is written.
+    pub fn new_canonical(stream: W) -> Self {
+        Serializer {
+            stream,
+            canonical: true,
+        }
     }
 }
 
@@ -114,7 +156,7 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
+    type SerializeMap = MapSerializer<'a, W>;
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
@@ -293,7 +335,8 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
         match len {
             Some(val) => {
                 self.serialize_u32(val.try_into().unwrap())?;
-                Ok(self)
+                let entries = self.canonical.then(|| Vec::with_capacity(val));
+                Ok(MapSerializer { ser: self, entries })
             }
             None => Err(Error::NotSupported),
         }
@@ -379,7 +422,16 @@ impl<'a, W: WordWrite> serde::ser::SerializeTupleVariant for &'a mut Serializer<
     }
 }
 
-impl<'a, W: WordWrite> serde::ser::SerializeMap for &'a mut Serializer<W> {
+/// Serializes the entries of a map
+///
+/// In canonical mode, the entries are buffered and written sorted by key when
+/// the map ends.
+pub struct MapSerializer<'a, W: WordWrite> {
+    ser: &'a mut Serializer<W>,
+    entries: Option<Vec<(Vec<u32>, Vec<u32>)>>,
+}
+
+impl<'a, W: WordWrite> serde::ser::SerializeMap for MapSerializer<'a, W> {
     type Ok = ();
     type Error = Error;
 
@@ -387,17 +439,39 @@ impl<'a, W: WordWrite> serde::ser::SerializeMap for &'a mut Serializer<W> {
     where
         T: serde::Serialize + ?Sized,
     {
-        key.serialize(&mut **self)
+        match &mut self.entries {
+            Some(entries) => {
+                entries.push((to_vec_canonical(key)?, Vec::new()));
+                Ok(())
+            }
+            None => key.serialize(&mut *self.ser),
+        }
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<()>
     where
         T: serde::Serialize + ?Sized,
     {
-        value.serialize(&mut **self)
+        match &mut self.entries {
+            Some(entries) => {
+                let (_, words) = entries
+                    .last_mut()
+                    .ok_or_else(|| Error::Custom("map value without a key".into()))?;
+                *words = to_vec_canonical(value)?;
+                Ok(())
+            }
+            None => value.serialize(&mut *self.ser),
+        }
     }
 
     fn end(self) -> Result<()> {
+        if let Some(mut entries) = self.entries {
+            entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, value) in entries {
+                self.ser.stream.write_words(&key)?;
+                self.ser.stream.write_words(&value)?;
+            }
+        }
         Ok(())
     }
 }